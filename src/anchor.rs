@@ -0,0 +1,222 @@
+use {
+    crate::{TextRange, TextSize},
+    std::{fmt, iter},
+};
+
+/// Which neighbouring character an [`Anchor`] sticks to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Bias {
+    /// The anchor stays after the character to its left: text inserted at
+    /// the anchor goes after it.
+    #[default]
+    Left,
+    /// The anchor stays before the character to its right: text inserted at
+    /// the anchor goes before it.
+    Right,
+}
+
+/// An offset which follows the text around it as the text is edited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Anchor {
+    /// The current offset of the anchor.
+    pub offset: TextSize,
+    /// How the anchor moves when text is inserted at, or replaced around, its
+    /// offset.
+    pub bias: Bias,
+}
+
+impl Anchor {
+    /// An anchor at `offset`.
+    #[inline]
+    pub fn new(offset: TextSize, bias: Bias) -> Anchor {
+        Anchor { offset, bias }
+    }
+}
+
+/// Identifies an anchor in an [`AnchorSet`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct AnchorId(usize);
+
+/// A collection of [`Anchor`]s which are moved together by edits.
+///
+/// # Examples
+///
+/// ```rust
+/// # use text_size::*;
+/// let mut anchors = AnchorSet::new();
+/// let cursor = anchors.insert(Anchor::new(4.into(), Bias::Right));
+/// let bookmark = anchors.insert(Anchor::new(10.into(), Bias::Left));
+///
+/// let mut builder = TextEdit::builder();
+/// builder.insert(4.into(), "very ".to_string());
+/// builder.delete(TextRange::new(8.into(), 12.into()));
+/// let edit = builder.finish().unwrap();
+///
+/// anchors.edit(edit.iter().map(|it| (it.delete, TextSize::of(it.insert.as_str()))));
+/// assert_eq!(anchors.get(cursor).unwrap().offset, TextSize::from(9));
+/// assert_eq!(anchors.get(bookmark).unwrap().offset, TextSize::from(13));
+/// ```
+#[derive(Clone, Default)]
+pub struct AnchorSet {
+    // Removed anchors leave a `None`, so that ids stay valid.
+    anchors: Vec<Option<Anchor>>,
+    len: usize,
+}
+
+impl fmt::Debug for AnchorSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl AnchorSet {
+    /// Creates an empty set.
+    #[inline]
+    pub fn new() -> AnchorSet {
+        AnchorSet::default()
+    }
+
+    /// The number of anchors in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the set has no anchors.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Adds an anchor, and returns its id.
+    pub fn insert(&mut self, anchor: Anchor) -> AnchorId {
+        self.anchors.push(Some(anchor));
+        self.len += 1;
+        AnchorId(self.anchors.len() - 1)
+    }
+
+    /// The anchor with this id, unless it was removed.
+    #[inline]
+    pub fn get(&self, id: AnchorId) -> Option<Anchor> {
+        *self.anchors.get(id.0)?
+    }
+
+    /// Removes the anchor with this id, and returns it.
+    pub fn remove(&mut self, id: AnchorId) -> Option<Anchor> {
+        let res = self.anchors.get_mut(id.0)?.take();
+        if res.is_some() {
+            self.len -= 1;
+        }
+        res
+    }
+
+    /// Iterates over the anchors, in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (AnchorId, Anchor)> + '_ {
+        let anchors = self.anchors.iter().enumerate();
+        anchors.filter_map(|(i, it)| Some((AnchorId(i), (*it)?)))
+    }
+
+    /// Moves every anchor through a batch of replacements.
+    ///
+    /// Each replacement is the range of the old text that is replaced, and the
+    /// length of the new text put in its place. Like the indels of a
+    /// [`TextEdit`](crate::TextEdit), all ranges refer to the text before any
+    /// of the replacements, and must be sorted and must not overlap.
+    ///
+    /// Anchors before or after all replaced ranges are shifted along with the
+    /// text. An anchor inside a replaced range, or at either of its ends,
+    /// moves to the start of the new text if it has [`Bias::Left`], and to the
+    /// end of the new text if it has [`Bias::Right`]. In particular, text
+    /// inserted exactly at an anchor ends up after it with [`Bias::Left`], and
+    /// before it with [`Bias::Right`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ranges are not sorted or overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let mut anchors = AnchorSet::new();
+    /// let left = anchors.insert(Anchor::new(3.into(), Bias::Left));
+    /// let right = anchors.insert(Anchor::new(3.into(), Bias::Right));
+    ///
+    /// // Replace `1..5` with 2 characters.
+    /// anchors.edit(vec![(TextRange::new(1.into(), 5.into()), 2.into())]);
+    /// assert_eq!(anchors.get(left).unwrap().offset, TextSize::from(1));
+    /// assert_eq!(anchors.get(right).unwrap().offset, TextSize::from(3));
+    /// ```
+    pub fn edit(&mut self, replacements: impl IntoIterator<Item = (TextRange, TextSize)>) {
+        let mut moves = Vec::new();
+        let (mut old_end, mut new_end) = (TextSize::from(0), TextSize::from(0));
+        for (range, len) in replacements {
+            assert!(
+                old_end <= range.start(),
+                "replaced ranges must be sorted and must not overlap, got {:?}",
+                range,
+            );
+            let new_start = new_end + (range.start() - old_end);
+            old_end = range.end();
+            new_end = new_start + len;
+            moves.push(Move {
+                old: range,
+                new: TextRange::new(new_start, new_end),
+            });
+        }
+        if moves.is_empty() {
+            return;
+        }
+        for anchor in self.anchors.iter_mut().flatten() {
+            anchor.offset = move_offset(&moves, *anchor);
+        }
+    }
+}
+
+/// A replacement, in terms of both the old and the new text.
+struct Move {
+    old: TextRange,
+    new: TextRange,
+}
+
+fn move_offset(moves: &[Move], anchor: Anchor) -> TextSize {
+    let offset = anchor.offset;
+    // The last move ending before `offset`, unless `offset` is in a replaced
+    // range.
+    let before = match anchor.bias {
+        Bias::Left => {
+            let i = moves.partition_point(|it| it.old.end() < offset);
+            match moves.get(i) {
+                Some(it) if it.old.start() <= offset => return it.new.start(),
+                _ => i.checked_sub(1),
+            }
+        }
+        Bias::Right => {
+            let i = moves.partition_point(|it| it.old.start() <= offset);
+            match i.checked_sub(1).map(|i| &moves[i]) {
+                Some(it) if offset <= it.old.end() => return it.new.end(),
+                _ => i.checked_sub(1),
+            }
+        }
+    };
+    match before {
+        Some(i) => moves[i].new.end() + (offset - moves[i].old.end()),
+        None => offset,
+    }
+}
+
+impl Extend<Anchor> for AnchorSet {
+    fn extend<I: IntoIterator<Item = Anchor>>(&mut self, iter: I) {
+        for anchor in iter {
+            self.insert(anchor);
+        }
+    }
+}
+
+impl iter::FromIterator<Anchor> for AnchorSet {
+    fn from_iter<I: IntoIterator<Item = Anchor>>(iter: I) -> AnchorSet {
+        let mut res = AnchorSet::new();
+        res.extend(iter);
+        res
+    }
+}
@@ -0,0 +1,24 @@
+use {
+    crate::{TextRange, TextSize},
+    bytemuck::{CheckedBitPattern, NoUninit, Pod, Zeroable},
+};
+
+// SAFETY: `TextSize` is a `repr(transparent)` wrapper around a `u32`, so every
+// bit pattern is a valid `TextSize`.
+unsafe impl Zeroable for TextSize {}
+unsafe impl Pod for TextSize {}
+
+// SAFETY: `TextRange` is a `repr(C)` pair of `TextSize`s, so it has no padding,
+// and all zeroes is the valid range `0..0`. It is deliberately not `Pod`: bit
+// patterns with `start > end` are rejected by `CheckedBitPattern` instead.
+unsafe impl Zeroable for TextRange {}
+unsafe impl NoUninit for TextRange {}
+
+unsafe impl CheckedBitPattern for TextRange {
+    type Bits = [u32; 2];
+
+    #[inline]
+    fn is_valid_bit_pattern(&[start, end]: &[u32; 2]) -> bool {
+        start <= end
+    }
+}
@@ -16,17 +16,64 @@
 //!
 //! Minimal Supported Rust Version: latest stable.
 
-#![forbid(unsafe_code)]
+#![cfg_attr(not(any(feature = "bytemuck", feature = "rkyv")), forbid(unsafe_code))]
+#![cfg_attr(any(feature = "bytemuck", feature = "rkyv"), deny(unsafe_code))]
 #![warn(missing_debug_implementations, missing_docs)]
 
+#[macro_use]
+mod macros;
+
+mod anchor;
+mod line_index;
+mod opt;
 mod range;
+mod range64;
+mod range_map;
+mod range_set;
 mod size;
+mod size64;
+mod snippet;
+mod source_map;
+mod tagged;
+mod text_edit;
 mod traits;
+mod utf16;
 
+#[cfg(feature = "bytemuck")]
+#[allow(unsafe_code)]
+mod bytemuck_impls;
+#[cfg(feature = "rkyv")]
+#[allow(unsafe_code)]
+mod rkyv_impls;
 #[cfg(feature = "serde")]
 mod serde_impls;
 
-pub use crate::{range::TextRange, size::TextSize, traits::TextLen};
+/// Alternative serde representations, for use with `#[serde(with = "...")]`.
+#[cfg(feature = "serde")]
+pub mod serde_helpers {
+    pub use crate::serde_impls::{text_range, text_size};
+}
+
+pub use crate::{
+    anchor::{Anchor, AnchorId, AnchorSet, Bias},
+    line_index::{LineCol, LineIndex, LineTerminators, WideEncoding, WideLineCol},
+    opt::{OptTextRange, OptTextSize, ReservedSizeError},
+    range::{InvalidRange, ParseRangeError, RangeError, TextRange},
+    range64::TextRange64,
+    range_map::{Query, TextRangeMap},
+    range_set::TextRangeSet,
+    size::TextSize,
+    size64::TextSize64,
+    snippet::Snippet,
+    source_map::{FileId, FileRange, SourceMap},
+    tagged::{Absolute, Relative, Space, Tagged},
+    text_edit::{Indel, OverlappingIndels, Side, TextEdit, TextEditBuilder},
+    traits::{TextLen, Utf16Len},
+    utf16::{Utf16Range, Utf16Size},
+};
+
+#[cfg(feature = "rkyv")]
+pub use crate::rkyv_impls::{ArchivedTextRange, ArchivedTextSize};
 
 #[cfg(target_pointer_width = "16")]
 compile_error!("text-size assumes usize >= u32 and does not work on 16-bit targets");
@@ -0,0 +1,542 @@
+use {
+    crate::{TextRange, TextSize},
+    std::{borrow::Cow, convert::TryFrom},
+};
+
+/// Maps flat [`TextSize`] offsets to `(line, column)` pairs and back.
+///
+/// Stores the offset at which each line starts, so lookups are a binary
+/// search. By default, lines are separated by `\n`; see [`LineTerminators`]
+/// for other options. A line terminator belongs to the line it ends.
+///
+/// Columns are counted in UTF-8 bytes. The index also remembers every
+/// non-ASCII character, so that columns can be converted to UTF-16 code units
+/// or to chars (see [`WideEncoding`]) in logarithmic time.
+///
+/// # Examples
+///
+/// ```rust
+/// # use text_size::*;
+/// let index = LineIndex::new("hello\nworld");
+///
+/// let offset = TextSize::from(8);
+/// let line_col = index.line_col(offset);
+/// assert_eq!(line_col, LineCol { line: 1, col: 2 });
+/// assert_eq!(index.offset(line_col), Some(offset));
+///
+/// assert_eq!(index.line_count(), 2);
+/// assert_eq!(index.line_range(0), Some(TextRange::new(0.into(), 5.into())));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineIndex {
+    // Invariant: non-empty, sorted by start, first line starts at zero, only
+    // the last line has no terminator.
+    lines: Vec<Line>,
+    len: TextSize,
+    terminators: LineTerminators,
+}
+
+/// A zero-based line and column pair, with the column counted in UTF-8 bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineCol {
+    /// Zero-based line number.
+    pub line: u32,
+    /// Zero-based column, in UTF-8 bytes from the start of the line.
+    pub col: u32,
+}
+
+/// A zero-based line and column pair, with the column counted in the units of
+/// some [`WideEncoding`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WideLineCol {
+    /// Zero-based line number.
+    pub line: u32,
+    /// Zero-based column, in code units of the encoding.
+    pub col: u32,
+}
+
+/// An encoding other than UTF-8 in which columns can be counted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WideEncoding {
+    /// UTF-16 code units, as used by LSP and JavaScript.
+    Utf16,
+    /// Unicode scalar values, that is, `char`s.
+    Utf32,
+}
+
+/// The set of character sequences that end a line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LineTerminators {
+    /// Only `\n`. This is the default.
+    #[default]
+    Lf,
+    /// `\n` and `\r\n`. A lone `\r` is part of the line.
+    LfCrLf,
+    /// `\n`, `\r\n`, `\r`, NEXT LINE (`U+0085`), LINE SEPARATOR (`U+2028`)
+    /// and PARAGRAPH SEPARATOR (`U+2029`).
+    ///
+    /// This is the union of what LSP and ECMAScript consider a line break.
+    Unicode,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Line {
+    start: TextSize,
+    terminator: Option<Terminator>,
+    chars: Vec<SpecialChar>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Terminator {
+    Lf,
+    CrLf,
+    Cr,
+    Nel,
+    Ls,
+    Ps,
+}
+
+impl Terminator {
+    fn as_str(self) -> &'static str {
+        match self {
+            Terminator::Lf => "\n",
+            Terminator::CrLf => "\r\n",
+            Terminator::Cr => "\r",
+            Terminator::Nel => "\u{85}",
+            Terminator::Ls => "\u{2028}",
+            Terminator::Ps => "\u{2029}",
+        }
+    }
+}
+
+/// A char within a line that is not a plain ASCII char: either non-ASCII, or
+/// a `\r` that does not end the line.
+///
+/// Lone `\r`s are remembered so that [`LineIndex::edit`] can tell when an
+/// edit joins one with a `\n`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct SpecialChar {
+    /// Start of the char, in UTF-8 bytes from the line start.
+    start: u32,
+    /// Length of the char in UTF-8 bytes.
+    len: u8,
+    /// Start of the char, in UTF-16 code units from the line start.
+    utf16_start: u32,
+    /// Start of the char, in chars from the line start.
+    utf32_start: u32,
+}
+
+impl SpecialChar {
+    fn end(self) -> u32 {
+        self.start + self.len as u32
+    }
+
+    fn wide_start(self, enc: WideEncoding) -> u32 {
+        match enc {
+            WideEncoding::Utf16 => self.utf16_start,
+            WideEncoding::Utf32 => self.utf32_start,
+        }
+    }
+
+    fn wide_len(self, enc: WideEncoding) -> u32 {
+        match enc {
+            WideEncoding::Utf16 if self.len == 4 => 2,
+            WideEncoding::Utf16 | WideEncoding::Utf32 => 1,
+        }
+    }
+
+    fn wide_end(self, enc: WideEncoding) -> u32 {
+        self.wide_start(enc) + self.wide_len(enc)
+    }
+}
+
+/// Fills in the wide columns of a line's chars from their UTF-8 columns.
+fn fix_wide_starts(chars: &mut [SpecialChar]) {
+    let mut utf16_delta = 0;
+    let mut utf32_delta = 0;
+    for c in chars {
+        c.utf16_start = c.start - utf16_delta;
+        c.utf32_start = c.start - utf32_delta;
+        utf16_delta += c.len as u32 - c.wide_len(WideEncoding::Utf16);
+        utf32_delta += c.len as u32 - c.wide_len(WideEncoding::Utf32);
+    }
+}
+
+/// Splits `text` into lines.
+///
+/// Line starts are relative to the start of `text`, and chars are relative
+/// to the start of their line. The last line never has a terminator.
+fn scan(text: &str, terminators: LineTerminators) -> Vec<Line> {
+    let new_line = |start: u32| Line {
+        start: start.into(),
+        terminator: None,
+        chars: Vec::new(),
+    };
+    let mut lines = vec![new_line(0)];
+
+    let unicode = terminators == LineTerminators::Unicode;
+    let mut line_start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let i = i as u32;
+        let terminator = match c {
+            '\n' => Some(Terminator::Lf),
+            '\r' if terminators != LineTerminators::Lf
+                && chars.peek().map(|&(_, c)| c) == Some('\n') =>
+            {
+                chars.next();
+                Some(Terminator::CrLf)
+            }
+            '\r' if unicode => Some(Terminator::Cr),
+            '\u{85}' if unicode => Some(Terminator::Nel),
+            '\u{2028}' if unicode => Some(Terminator::Ls),
+            '\u{2029}' if unicode => Some(Terminator::Ps),
+            _ => None,
+        };
+
+        let line = lines.last_mut().unwrap();
+        match terminator {
+            Some(terminator) => {
+                line.terminator = Some(terminator);
+                line_start = i + terminator.as_str().len() as u32;
+                lines.push(new_line(line_start));
+            }
+            None if c == '\r' || !c.is_ascii() => line.chars.push(SpecialChar {
+                start: i - line_start,
+                len: c.len_utf8() as u8,
+                utf16_start: 0,
+                utf32_start: 0,
+            }),
+            None => (),
+        }
+    }
+
+    for line in &mut lines {
+        fix_wide_starts(&mut line.chars);
+    }
+    lines
+}
+
+impl LineIndex {
+    /// Builds the index for `text`, with lines separated by `\n`.
+    pub fn new(text: &str) -> LineIndex {
+        LineIndex::with_terminators(text, LineTerminators::default())
+    }
+
+    /// Builds the index for `text`, with lines separated by the given
+    /// `terminators`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let text = "one\r\ntwo\rthree";
+    ///
+    /// let index = LineIndex::with_terminators(text, LineTerminators::LfCrLf);
+    /// assert_eq!(index.line_count(), 2);
+    /// assert_eq!(index.line_range(0), Some(TextRange::new(0.into(), 3.into())));
+    ///
+    /// let index = LineIndex::with_terminators(text, LineTerminators::Unicode);
+    /// assert_eq!(index.line_count(), 3);
+    /// assert_eq!(index.line_range(2), Some(TextRange::new(9.into(), 14.into())));
+    /// ```
+    pub fn with_terminators(text: &str, terminators: LineTerminators) -> LineIndex {
+        let len = TextSize::of(text);
+        LineIndex {
+            lines: scan(text, terminators),
+            len,
+            terminators,
+        }
+    }
+
+    /// Updates the index after `delete` was replaced with `insert` in the
+    /// text.
+    ///
+    /// Only `insert` is scanned for line terminators; the starts of the
+    /// following lines are shifted. The result is the same as building a new
+    /// index for the edited text.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delete` is out of bounds of the text.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let mut text = String::from("hello\nworld");
+    /// let mut index = LineIndex::new(&text);
+    ///
+    /// let delete = TextRange::new(2.into(), 8.into());
+    /// let insert = "y\nbig\nw";
+    /// text.replace_range(std::ops::Range::<usize>::from(delete), insert);
+    /// index.edit(delete, insert);
+    ///
+    /// assert_eq!(text, "hey\nbig\nwrld");
+    /// assert_eq!(index, LineIndex::new(&text));
+    /// ```
+    pub fn edit(&mut self, delete: TextRange, insert: &str) {
+        assert!(
+            delete.end() <= self.len,
+            "range {:?} is out of bounds of text of length {:?}",
+            delete,
+            self.len,
+        );
+
+        // A `\r` right before the edit or a `\n` right after it may form a
+        // `\r\n` with the other side, so they are rescanned with `insert`.
+        let mut delete = delete;
+        let mut insert = Cow::Borrowed(insert);
+        let cr = TextSize::of('\r');
+        if delete.start() >= cr && self.cr_or_lf_at(delete.start() - cr) == Some(b'\r') {
+            delete = TextRange::new(delete.start() - cr, delete.end());
+            insert.to_mut().insert(0, '\r');
+        }
+        if self.cr_or_lf_at(delete.end()) == Some(b'\n') {
+            delete = TextRange::new(delete.start(), delete.end() + TextSize::of('\n'));
+            insert.to_mut().push('\n');
+        }
+
+        let first = self.line_col(delete.start()).line as usize;
+        let last = self.line_col(delete.end()).line as usize;
+        let start_col = u32::from(delete.start() - self.lines[first].start);
+        let end_col = u32::from(delete.end() - self.lines[last].start);
+        let insert_len = TextSize::of(insert.as_ref());
+
+        let mut new_lines = scan(&insert, self.terminators);
+        let added_lines = new_lines.len() - 1;
+        for line in &mut new_lines {
+            line.start += delete.start();
+        }
+
+        // The first new line keeps the part of the old line before the edit.
+        let head = self.lines[first]
+            .chars
+            .iter()
+            .copied()
+            .take_while(|c| c.start < start_col);
+        let first_chars = head
+            .chain(new_lines[0].chars.iter().map(|&c| SpecialChar {
+                start: c.start + start_col,
+                ..c
+            }))
+            .collect();
+        new_lines[0].start = self.lines[first].start;
+        new_lines[0].chars = first_chars;
+
+        // The last new line keeps the part of the old line after the edit.
+        let last_line = &mut new_lines[added_lines];
+        let tail_col = u32::from(delete.start() + insert_len - last_line.start);
+        let tail = self.lines[last]
+            .chars
+            .iter()
+            .filter(|c| c.start >= end_col)
+            .map(|&c| SpecialChar {
+                start: c.start - end_col + tail_col,
+                ..c
+            });
+        last_line.chars.extend(tail);
+        last_line.terminator = self.lines[last].terminator;
+
+        fix_wide_starts(&mut new_lines[0].chars);
+        fix_wide_starts(&mut new_lines[added_lines].chars);
+        self.lines.splice(first..=last, new_lines);
+        for line in &mut self.lines[first + 1 + added_lines..] {
+            line.start = line.start - delete.len() + insert_len;
+        }
+        self.len = self.len - delete.len() + insert_len;
+    }
+
+    /// The byte at `offset`, if it is known to be a `\r` or a `\n`.
+    fn cr_or_lf_at(&self, offset: TextSize) -> Option<u8> {
+        if offset >= self.len {
+            return None;
+        }
+        let line = self.line_col(offset).line;
+        let range = self.line_range(line)?;
+        let line = &self.lines[line as usize];
+        if offset < range.end() {
+            let col = u32::from(offset - range.start());
+            let i = line.chars.partition_point(|c| c.start < col);
+            match line.chars.get(i) {
+                Some(c) if c.start == col && c.len == 1 => Some(b'\r'),
+                _ => None,
+            }
+        } else {
+            let i = usize::from(offset - range.end());
+            let terminator = line.terminator?.as_str().as_bytes();
+            terminator
+                .get(i)
+                .copied()
+                .filter(|&b| b == b'\r' || b == b'\n')
+        }
+    }
+
+    /// The number of lines in the text.
+    ///
+    /// This is always at least one: the empty text has a single empty line,
+    /// and a trailing line terminator starts another empty line.
+    #[inline]
+    pub fn line_count(&self) -> u32 {
+        self.lines.len() as u32
+    }
+
+    /// Converts an offset into a line and column.
+    ///
+    /// An offset inside a multi-byte line terminator, such as between `\r`
+    /// and `\n`, gets a column past the end of the line's content.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` is past the end of the text.
+    pub fn line_col(&self, offset: TextSize) -> LineCol {
+        assert!(
+            offset <= self.len,
+            "offset {:?} is out of bounds of text of length {:?}",
+            offset,
+            self.len,
+        );
+        let line = self.lines.partition_point(|it| it.start <= offset) - 1;
+        let col = offset - self.lines[line].start;
+        LineCol {
+            line: line as u32,
+            col: col.into(),
+        }
+    }
+
+    /// Converts a line and column into an offset.
+    ///
+    /// Returns `None` if the line does not exist or the column is past the
+    /// end of the line's content. The column of the line's end (where the
+    /// terminator is) is accepted.
+    pub fn offset(&self, line_col: LineCol) -> Option<TextSize> {
+        let range = self.line_range(line_col.line)?;
+        let offset = range.start().checked_add(line_col.col.into())?;
+        if range.contains_inclusive(offset) {
+            Some(offset)
+        } else {
+            None
+        }
+    }
+
+    /// The range of the given line's content, excluding its terminator.
+    ///
+    /// Returns `None` if the line does not exist.
+    pub fn line_range(&self, line: u32) -> Option<TextRange> {
+        let range = self.line_range_with_terminator(line)?;
+        let terminator = self.lines[line as usize].terminator;
+        let terminator_len = terminator.map_or(0, |it| it.as_str().len() as u32);
+        Some(TextRange::new(
+            range.start(),
+            range.end() - TextSize::from(terminator_len),
+        ))
+    }
+
+    /// The range of the given line, including its terminator.
+    ///
+    /// Returns `None` if the line does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let index = LineIndex::with_terminators("one\r\ntwo", LineTerminators::LfCrLf);
+    /// assert_eq!(
+    ///     index.line_range_with_terminator(0),
+    ///     Some(TextRange::new(0.into(), 5.into())),
+    /// );
+    /// assert_eq!(
+    ///     index.line_range_with_terminator(1),
+    ///     Some(TextRange::new(5.into(), 8.into())),
+    /// );
+    /// ```
+    pub fn line_range_with_terminator(&self, line: u32) -> Option<TextRange> {
+        let line = usize::try_from(line).ok()?;
+        let start = self.lines.get(line)?.start;
+        let end = match self.lines.get(line + 1) {
+            Some(next) => next.start,
+            None => self.len,
+        };
+        Some(TextRange::new(start, end))
+    }
+
+    /// Converts a UTF-8 column into a column in the `enc` encoding.
+    ///
+    /// Returns `None` if the line does not exist, the column is past the end
+    /// of the line, or the column is in the middle of a char.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let index = LineIndex::new("let crab = '🦀';");
+    /// let line_col = index.line_col(TextSize::from(16));
+    /// assert_eq!(line_col, LineCol { line: 0, col: 16 });
+    ///
+    /// let utf16 = index.to_wide(WideEncoding::Utf16, line_col);
+    /// assert_eq!(utf16, Some(WideLineCol { line: 0, col: 14 }));
+    /// let utf32 = index.to_wide(WideEncoding::Utf32, line_col);
+    /// assert_eq!(utf32, Some(WideLineCol { line: 0, col: 13 }));
+    /// ```
+    pub fn to_wide(&self, enc: WideEncoding, line_col: LineCol) -> Option<WideLineCol> {
+        let range = self.line_range(line_col.line)?;
+        let col = line_col.col;
+        if col > u32::from(range.len()) {
+            return None;
+        }
+
+        let chars = &self.lines[line_col.line as usize].chars;
+        let i = chars.partition_point(|c| c.start < col);
+        let col = match i.checked_sub(1).map(|i| chars[i]) {
+            Some(c) if col < c.end() => return None,
+            Some(c) => c.wide_end(enc) + (col - c.end()),
+            None => col,
+        };
+        Some(WideLineCol {
+            line: line_col.line,
+            col,
+        })
+    }
+
+    /// Converts a column in the `enc` encoding into a UTF-8 column.
+    ///
+    /// Returns `None` if the line does not exist, the column is past the end
+    /// of the line, or the column is in the middle of a UTF-16 surrogate pair.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let index = LineIndex::new("🦀");
+    /// let line_col = |col| WideLineCol { line: 0, col };
+    ///
+    /// assert_eq!(
+    ///     index.to_utf8(WideEncoding::Utf16, line_col(0)),
+    ///     Some(LineCol { line: 0, col: 0 })
+    /// );
+    /// assert_eq!(index.to_utf8(WideEncoding::Utf16, line_col(1)), None);
+    /// assert_eq!(
+    ///     index.to_utf8(WideEncoding::Utf16, line_col(2)),
+    ///     Some(LineCol { line: 0, col: 4 })
+    /// );
+    /// ```
+    pub fn to_utf8(&self, enc: WideEncoding, line_col: WideLineCol) -> Option<LineCol> {
+        let range = self.line_range(line_col.line)?;
+        let col = line_col.col;
+
+        let chars = &self.lines[line_col.line as usize].chars;
+        let i = chars.partition_point(|c| c.wide_start(enc) < col);
+        let col = match i.checked_sub(1).map(|i| chars[i]) {
+            Some(c) if col < c.wide_end(enc) => return None,
+            Some(c) => c.end().checked_add(col - c.wide_end(enc))?,
+            None => col,
+        };
+        if col > u32::from(range.len()) {
+            return None;
+        }
+        Some(LineCol {
+            line: line_col.line,
+            col,
+        })
+    }
+}
@@ -0,0 +1,1275 @@
+//! The API shared by size and range types.
+//!
+//! [`TextSize`](crate::TextSize), [`TextSize64`](crate::TextSize64) and
+//! [`Utf16Size`](crate::Utf16Size), and their ranges, are all generated here,
+//! so that a fix to one of them is a fix to all of them.
+
+/// The API of a size `$Size { raw: $Int }`, counted in `$unit`.
+macro_rules! size_impls {
+    ($Size:ident($Int:ident), $unit:literal) => {
+        impl std::fmt::Debug for $Size {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.raw)
+            }
+        }
+
+        impl std::fmt::Display for $Size {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Display::fmt(&self.raw, f)
+            }
+        }
+
+        #[doc = concat!(" Parses a number of ", $unit, ", like `", stringify!($Int), "` does.")]
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # use text_size::*;
+        #[doc = concat!(" let size: ", stringify!($Size), " = \"92\".parse().unwrap();")]
+        #[doc = concat!(" assert_eq!(size, ", stringify!($Size), "::from(92));")]
+        /// assert_eq!(size.to_string(), "92");
+        #[doc = concat!(" assert!(\"-1\".parse::<", stringify!($Size), ">().is_err());")]
+        /// ```
+        impl std::str::FromStr for $Size {
+            type Err = std::num::ParseIntError;
+            #[inline]
+            fn from_str(s: &str) -> Result<$Size, std::num::ParseIntError> {
+                s.parse().map(|raw| $Size { raw })
+            }
+        }
+
+        impl $Size {
+            #[doc = concat!(" Creates a new `", stringify!($Size), "` from a number of ", $unit, ".")]
+            ///
+            #[doc = concat!(" Unlike the `From<", stringify!($Int), ">` conversion, this can be used in constants.")]
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            #[doc = concat!(" const TAB_WIDTH: ", stringify!($Size), " = ", stringify!($Size), "::new(4);")]
+            #[doc = concat!(" assert_eq!(TAB_WIDTH, ", stringify!($Size), "::from(4));")]
+            /// ```
+            #[inline]
+            pub const fn new(raw: $Int) -> $Size {
+                $Size { raw }
+            }
+        }
+
+        /// Methods to act like a primitive integer type, where reasonably applicable.
+        //  Last updated for parity with Rust 1.95.0. Left out on purpose: bit
+        //  manipulation, powers, roots and logarithms, negation and the
+        //  `*_signed` methods, as there is no signed text size, the `*_euclid`
+        //  methods, which are the same as the plain ones for unsigned integers,
+        //  and the `strict_*` and `unchecked_*` variants.
+        impl $Size {
+            /// The smallest text size, zero.
+            pub const ZERO: $Size = $Size { raw: 0 };
+
+            #[doc = concat!(" The largest text size, `", stringify!($Int), "::MAX`.")]
+            pub const MAX: $Size = $Size { raw: $Int::MAX };
+
+            /// Checked addition. Returns `None` if overflow occurred.
+            #[inline]
+            pub const fn checked_add(self, rhs: $Size) -> Option<$Size> {
+                // HACK for const fn: no `Option::map`
+                match self.raw.checked_add(rhs.raw) {
+                    Some(raw) => Some($Size { raw }),
+                    None => None,
+                }
+            }
+
+            /// Checked subtraction. Returns `None` if overflow occurred.
+            #[inline]
+            pub const fn checked_sub(self, rhs: $Size) -> Option<$Size> {
+                // HACK for const fn: no `Option::map`
+                match self.raw.checked_sub(rhs.raw) {
+                    Some(raw) => Some($Size { raw }),
+                    None => None,
+                }
+            }
+
+            /// Checked multiplication. Returns `None` if overflow occurred.
+            #[inline]
+            pub fn checked_mul(self, rhs: $Int) -> Option<$Size> {
+                self.raw.checked_mul(rhs).map(|raw| $Size { raw })
+            }
+
+            /// Checked division. Returns `None` if `rhs == 0`.
+            #[inline]
+            pub fn checked_div(self, rhs: $Int) -> Option<$Size> {
+                self.raw.checked_div(rhs).map(|raw| $Size { raw })
+            }
+
+            /// Checked remainder. Returns `None` if `rhs == 0`.
+            #[inline]
+            pub fn checked_rem(self, rhs: $Int) -> Option<$Size> {
+                self.raw.checked_rem(rhs).map(|raw| $Size { raw })
+            }
+
+            /// Checked [`next_multiple_of`](Self::next_multiple_of). Returns `None`
+            /// if `rhs == 0` or if overflow occurred.
+            #[inline]
+            pub fn checked_next_multiple_of(self, rhs: $Int) -> Option<$Size> {
+                self.raw.checked_next_multiple_of(rhs).map(|raw| $Size { raw })
+            }
+
+            #[doc = concat!(" Saturating addition. Returns [`", stringify!($Size), "::MAX`] if overflow occurred.")]
+            #[inline]
+            pub fn saturating_add(self, rhs: $Size) -> $Size {
+                $Size {
+                    raw: self.raw.saturating_add(rhs.raw),
+                }
+            }
+
+            #[doc = concat!(" Saturating subtraction. Returns [`", stringify!($Size), "::ZERO`] if overflow")]
+            /// occurred.
+            #[inline]
+            pub fn saturating_sub(self, rhs: $Size) -> $Size {
+                $Size {
+                    raw: self.raw.saturating_sub(rhs.raw),
+                }
+            }
+
+            #[doc = concat!(" Saturating multiplication. Returns [`", stringify!($Size), "::MAX`] if overflow")]
+            /// occurred.
+            #[inline]
+            pub fn saturating_mul(self, rhs: $Int) -> $Size {
+                $Size {
+                    raw: self.raw.saturating_mul(rhs),
+                }
+            }
+
+            /// Saturating division. This is the same as plain division, which can
+            /// not overflow.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `rhs == 0`.
+            #[inline]
+            pub fn saturating_div(self, rhs: $Int) -> $Size {
+                $Size {
+                    raw: self.raw.saturating_div(rhs),
+                }
+            }
+
+            /// Wrapping (modular) addition.
+            #[inline]
+            pub fn wrapping_add(self, rhs: $Size) -> $Size {
+                $Size {
+                    raw: self.raw.wrapping_add(rhs.raw),
+                }
+            }
+
+            /// Wrapping (modular) subtraction.
+            #[inline]
+            pub fn wrapping_sub(self, rhs: $Size) -> $Size {
+                $Size {
+                    raw: self.raw.wrapping_sub(rhs.raw),
+                }
+            }
+
+            /// Wrapping (modular) multiplication.
+            #[inline]
+            pub fn wrapping_mul(self, rhs: $Int) -> $Size {
+                $Size {
+                    raw: self.raw.wrapping_mul(rhs),
+                }
+            }
+
+            /// Wrapping division. This is the same as plain division, which can
+            /// not overflow.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `rhs == 0`.
+            #[inline]
+            pub fn wrapping_div(self, rhs: $Int) -> $Size {
+                $Size {
+                    raw: self.raw.wrapping_div(rhs),
+                }
+            }
+
+            /// Wrapping remainder. This is the same as the plain remainder, which
+            /// can not overflow.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `rhs == 0`.
+            #[inline]
+            pub fn wrapping_rem(self, rhs: $Int) -> $Size {
+                $Size {
+                    raw: self.raw.wrapping_rem(rhs),
+                }
+            }
+
+            /// Addition which also returns whether overflow occurred, in which case
+            /// the result is wrapped.
+            #[inline]
+            pub fn overflowing_add(self, rhs: $Size) -> ($Size, bool) {
+                let (raw, overflow) = self.raw.overflowing_add(rhs.raw);
+                ($Size { raw }, overflow)
+            }
+
+            /// Subtraction which also returns whether overflow occurred, in which case
+            /// the result is wrapped.
+            #[inline]
+            pub fn overflowing_sub(self, rhs: $Size) -> ($Size, bool) {
+                let (raw, overflow) = self.raw.overflowing_sub(rhs.raw);
+                ($Size { raw }, overflow)
+            }
+
+            /// Multiplication which also returns whether overflow occurred, in which
+            /// case the result is wrapped.
+            #[inline]
+            pub fn overflowing_mul(self, rhs: $Int) -> ($Size, bool) {
+                let (raw, overflow) = self.raw.overflowing_mul(rhs);
+                ($Size { raw }, overflow)
+            }
+
+            /// Division which also returns whether overflow occurred, which it never
+            /// does.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `rhs == 0`.
+            #[inline]
+            pub fn overflowing_div(self, rhs: $Int) -> ($Size, bool) {
+                let (raw, overflow) = self.raw.overflowing_div(rhs);
+                ($Size { raw }, overflow)
+            }
+
+            /// Remainder which also returns whether overflow occurred, which it never
+            /// does.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `rhs == 0`.
+            #[inline]
+            pub fn overflowing_rem(self, rhs: $Int) -> ($Size, bool) {
+                let (raw, overflow) = self.raw.overflowing_rem(rhs);
+                ($Size { raw }, overflow)
+            }
+
+            /// Division rounding up.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `rhs == 0`.
+            #[inline]
+            pub fn div_ceil(self, rhs: $Int) -> $Size {
+                $Size {
+                    raw: self.raw.div_ceil(rhs),
+                }
+            }
+
+            /// The smallest multiple of `rhs` at or after `self`, such as the next
+            /// tab stop.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `rhs == 0`, and on overflow in debug mode.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            #[doc = concat!(" assert_eq!(", stringify!($Size), "::from(5).next_multiple_of(4), ", stringify!($Size), "::from(8));")]
+            #[doc = concat!(" assert_eq!(", stringify!($Size), "::from(8).next_multiple_of(4), ", stringify!($Size), "::from(8));")]
+            /// ```
+            #[inline]
+            pub fn next_multiple_of(self, rhs: $Int) -> $Size {
+                $Size {
+                    raw: self.raw.next_multiple_of(rhs),
+                }
+            }
+
+            /// Check if `self` is a multiple of `rhs`. Zero is only a multiple of
+            /// itself.
+            #[inline]
+            pub fn is_multiple_of(self, rhs: $Int) -> bool {
+                self.raw.is_multiple_of(rhs)
+            }
+
+            /// The absolute difference between `self` and `other`.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            #[doc = concat!(" assert_eq!(", stringify!($Size), "::from(3).abs_diff(", stringify!($Size), "::from(10)), ", stringify!($Size), "::from(7));")]
+            #[doc = concat!(" assert_eq!(", stringify!($Size), "::from(10).abs_diff(", stringify!($Size), "::from(3)), ", stringify!($Size), "::from(7));")]
+            /// ```
+            #[inline]
+            pub fn abs_diff(self, other: $Size) -> $Size {
+                $Size {
+                    raw: self.raw.abs_diff(other.raw),
+                }
+            }
+
+            /// The average of `self` and `rhs`, rounded down, without overflowing.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            #[doc = concat!(" let (a, b) = (", stringify!($Size), "::from(3), ", stringify!($Size), "::MAX);")]
+            #[doc = concat!(" assert_eq!(a.midpoint(", stringify!($Size), "::from(10)), ", stringify!($Size), "::from(6));")]
+            #[doc = concat!(" assert_eq!(b.midpoint(b), ", stringify!($Size), "::MAX);")]
+            /// ```
+            #[inline]
+            pub fn midpoint(self, rhs: $Size) -> $Size {
+                $Size {
+                    raw: self.raw.midpoint(rhs.raw),
+                }
+            }
+        }
+
+        impl From<$Int> for $Size {
+            #[inline]
+            fn from(raw: $Int) -> Self {
+                $Size { raw }
+            }
+        }
+
+        impl From<$Size> for $Int {
+            #[inline]
+            fn from(value: $Size) -> Self {
+                value.raw
+            }
+        }
+
+        impl std::convert::TryFrom<usize> for $Size {
+            type Error = std::num::TryFromIntError;
+            #[inline]
+            fn try_from(value: usize) -> Result<Self, std::num::TryFromIntError> {
+                Ok(<$Int as std::convert::TryFrom<usize>>::try_from(value)?.into())
+            }
+        }
+
+        size_ops!(impl Add for $Size by fn add = +);
+        size_ops!(impl Sub for $Size by fn sub = -);
+        size_ops!(impl Rem for $Size by fn rem = %);
+
+        scalar_ops!(impl Mul<$Int> for $Size by fn mul = *);
+        scalar_ops!(impl Div<$Int> for $Size by fn div = /);
+        scalar_ops!(impl Rem<$Int> for $Size by fn rem = %);
+
+        impl std::ops::Mul<$Size> for $Int {
+            type Output = $Size;
+            #[inline]
+            fn mul(self, other: $Size) -> $Size {
+                other * self
+            }
+        }
+
+        assign_ops!(impl AddAssign<A> for $Size by fn add_assign = + via Add);
+        assign_ops!(impl SubAssign<S> for $Size by fn sub_assign = - via Sub);
+        assign_ops!(impl MulAssign<M> for $Size by fn mul_assign = * via Mul);
+        assign_ops!(impl DivAssign<D> for $Size by fn div_assign = / via Div);
+        assign_ops!(impl RemAssign<R> for $Size by fn rem_assign = % via Rem);
+
+        impl<A> std::iter::Sum<A> for $Size
+        where
+            $Size: std::ops::Add<A, Output = $Size>,
+        {
+            #[inline]
+            fn sum<I: Iterator<Item = A>>(iter: I) -> $Size {
+                iter.fold(0.into(), std::ops::Add::add)
+            }
+        }
+    };
+}
+
+/// The UTF-8 specific API of a size, whose length of a [`TextLen`] is given by
+/// `$text_len`.
+///
+/// [`TextLen`]: crate::TextLen
+macro_rules! text_size_impls {
+    ($Size:ident, $text_len:ident) => {
+        impl $Size {
+            /// The text size of some text-like object.
+            ///
+            /// Accepts `char`, `&str`, references to other std string types, and any
+            /// other type implementing [`TextLen`](crate::TextLen).
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            #[doc = concat!(" let char_size = ", stringify!($Size), "::of('🦀');")]
+            #[doc = concat!(" assert_eq!(char_size, ", stringify!($Size), "::from(4));")]
+            ///
+            #[doc = concat!(" let str_size = ", stringify!($Size), "::of(\"rust-analyzer\");")]
+            #[doc = concat!(" assert_eq!(str_size, ", stringify!($Size), "::from(13));")]
+            /// ```
+            #[inline]
+            pub fn of<T: $crate::TextLen>(text: T) -> $Size {
+                text.$text_len()
+            }
+        }
+
+        /// Char boundary methods.
+        impl $Size {
+            /// The closest char boundary in `text` at or before this offset.
+            ///
+            /// Offsets past the end of `text` are rounded down to its length.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            /// let text = "a🦀b";
+            #[doc = concat!(" assert_eq!(", stringify!($Size), "::from(3).floor_char_boundary(text), ", stringify!($Size), "::from(1));")]
+            #[doc = concat!(" assert_eq!(", stringify!($Size), "::from(5).floor_char_boundary(text), ", stringify!($Size), "::from(5));")]
+            #[doc = concat!(" assert_eq!(", stringify!($Size), "::from(9).floor_char_boundary(text), ", stringify!($Size), "::from(6));")]
+            /// ```
+            pub fn floor_char_boundary(self, text: &str) -> $Size {
+                let mut offset =
+                    std::cmp::min($crate::macros::usize_saturating(self.raw), text.len());
+                while !text.is_char_boundary(offset) {
+                    offset -= 1;
+                }
+                <$Size as std::convert::TryFrom<usize>>::try_from(offset).unwrap()
+            }
+
+            /// The closest char boundary in `text` at or after this offset.
+            ///
+            /// Offsets past the end of `text` are rounded down to its length.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            /// let text = "a🦀b";
+            #[doc = concat!(" assert_eq!(", stringify!($Size), "::from(3).ceil_char_boundary(text), ", stringify!($Size), "::from(5));")]
+            #[doc = concat!(" assert_eq!(", stringify!($Size), "::from(5).ceil_char_boundary(text), ", stringify!($Size), "::from(5));")]
+            #[doc = concat!(" assert_eq!(", stringify!($Size), "::from(9).ceil_char_boundary(text), ", stringify!($Size), "::from(6));")]
+            /// ```
+            pub fn ceil_char_boundary(self, text: &str) -> $Size {
+                let mut offset =
+                    std::cmp::min($crate::macros::usize_saturating(self.raw), text.len());
+                while !text.is_char_boundary(offset) {
+                    offset += 1;
+                }
+                <$Size as std::convert::TryFrom<usize>>::try_from(offset).unwrap()
+            }
+        }
+    };
+}
+
+/// The API of a range `$Range { start: $Size, end: $Size }`.
+macro_rules! range_impls {
+    (
+        $Range:ident($Size:ident)
+        $(, at_example: $text:literal => $sliced:literal)?
+        $(, offset_note: { $(#[$note:meta])* })?
+    ) => {
+        impl std::fmt::Debug for $Range {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}..{}", self.start().raw, self.end().raw)
+            }
+        }
+
+        impl std::fmt::Display for $Range {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}..{}", self.start(), self.end())
+            }
+        }
+
+        impl $Range {
+            #[doc = concat!(" Creates a new `", stringify!($Range), "` with the given `start` and `end` (`start..end`).")]
+            ///
+            /// # Panics
+            ///
+            /// Panics if `end < start`.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            #[doc = concat!(" let start = ", stringify!($Size), "::from(5);")]
+            #[doc = concat!(" let end = ", stringify!($Size), "::from(10);")]
+            #[doc = concat!(" let range = ", stringify!($Range), "::new(start, end);")]
+            ///
+            /// assert_eq!(range.start(), start);
+            /// assert_eq!(range.end(), end);
+            /// assert_eq!(range.len(), end - start);
+            ///
+            /// // also usable in constants
+            #[doc = concat!(" const KEYWORD: ", stringify!($Range), " = ", stringify!($Range), "::new(", stringify!($Size), "::new(0), ", stringify!($Size), "::new(2));")]
+            #[doc = concat!(" assert_eq!(KEYWORD.len(), ", stringify!($Size), "::from(2));")]
+            /// ```
+            #[inline]
+            pub const fn new(start: $Size, end: $Size) -> $Range {
+                // HACK for const fn: math on primitives only
+                assert!(start.raw <= end.raw);
+                $Range { start, end }
+            }
+
+            #[doc = concat!(" Creates a new `", stringify!($Range), "` with the given `start` and `end` (`start..end`),")]
+            /// or an error if `end < start`.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            #[doc = concat!(" let range = ", stringify!($Range), "::try_new(5.into(), 10.into());")]
+            #[doc = concat!(" assert_eq!(range, Ok(", stringify!($Range), "::new(5.into(), 10.into())));")]
+            ///
+            #[doc = concat!(" let err = ", stringify!($Range), "::try_new(10.into(), 5.into()).unwrap_err();")]
+            /// assert_eq!(err.to_string(), "invalid range: 10..5");
+            /// ```
+            #[inline]
+            pub fn try_new(
+                start: $Size,
+                end: $Size,
+            ) -> Result<$Range, $crate::InvalidRange<$Size>> {
+                if end < start {
+                    return Err($crate::InvalidRange { start, end });
+                }
+                Ok($Range { start, end })
+            }
+
+            #[doc = concat!(" Create a new `", stringify!($Range), "` with the given `offset` and `len` (`offset..offset + len`).")]
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            $(
+            #[doc = concat!(" let text = ", stringify!($text), ";")]
+            ///
+            )?
+            #[doc = concat!(" let offset = ", stringify!($Size), "::from(2);")]
+            #[doc = concat!(" let length = ", stringify!($Size), "::from(5);")]
+            #[doc = concat!(" let range = ", stringify!($Range), "::at(offset, length);")]
+            ///
+            #[doc = concat!(" assert_eq!(range, ", stringify!($Range), "::new(offset, offset + length));")]
+            $(
+            #[doc = concat!(" assert_eq!(&text[range], ", stringify!($sliced), ")")]
+            )?
+            /// ```
+            #[inline]
+            pub const fn at(offset: $Size, len: $Size) -> $Range {
+                // HACK for const fn: math on primitives only
+                $Range::new(offset, $Size::new(offset.raw + len.raw))
+            }
+
+            /// Create a zero-length range at the specified offset (`offset..offset`).
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            #[doc = concat!(" let point: ", stringify!($Size), ";")]
+            #[doc = concat!(" # point = ", stringify!($Size), "::from(3);")]
+            #[doc = concat!(" let range = ", stringify!($Range), "::empty(point);")]
+            /// assert!(range.is_empty());
+            #[doc = concat!(" assert_eq!(range, ", stringify!($Range), "::new(point, point));")]
+            /// ```
+            #[inline]
+            pub const fn empty(offset: $Size) -> $Range {
+                $Range {
+                    start: offset,
+                    end: offset,
+                }
+            }
+
+            /// Create a range up to the given end (`..end`).
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            #[doc = concat!(" let point: ", stringify!($Size), ";")]
+            #[doc = concat!(" # point = ", stringify!($Size), "::from(12);")]
+            #[doc = concat!(" let range = ", stringify!($Range), "::up_to(point);")]
+            ///
+            /// assert_eq!(range.len(), point);
+            #[doc = concat!(" assert_eq!(range, ", stringify!($Range), "::new(0.into(), point));")]
+            #[doc = concat!(" assert_eq!(range, ", stringify!($Range), "::at(0.into(), point));")]
+            /// ```
+            #[inline]
+            pub const fn up_to(end: $Size) -> $Range {
+                $Range {
+                    start: $Size::ZERO,
+                    end,
+                }
+            }
+        }
+
+        /// Parses a range written as `start..end`, `start..=end`, `start+len`, or as
+        /// a bare offset, which is an empty range.
+        ///
+        /// # Examples
+        ///
+        /// ```rust
+        /// # use text_size::*;
+        #[doc = concat!(" let range = ", stringify!($Range), "::new(5.into(), 10.into());")]
+        /// assert_eq!("5..10".parse(), Ok(range));
+        /// assert_eq!("5..=9".parse(), Ok(range));
+        /// assert_eq!("5+5".parse(), Ok(range));
+        #[doc = concat!(" assert_eq!(\"5\".parse(), Ok(", stringify!($Range), "::empty(5.into())));")]
+        /// assert_eq!(range.to_string(), "5..10");
+        ///
+        #[doc = concat!(" assert!(\"10..5\".parse::<", stringify!($Range), ">().is_err());")]
+        /// ```
+        impl std::str::FromStr for $Range {
+            type Err = $crate::ParseRangeError<$Size>;
+            fn from_str(s: &str) -> Result<$Range, $crate::ParseRangeError<$Size>> {
+                use $crate::ParseRangeError;
+                let offset = |s: &str| {
+                    // The integer types accept a leading `+`, which would make
+                    // `5++5` a valid range.
+                    if s.starts_with(['+', '-']) {
+                        return Err(ParseRangeError::Sign);
+                    }
+                    s.parse::<$Size>().map_err(ParseRangeError::Int)
+                };
+                let (start, end) = if let Some((start, end)) = s.split_once("..=") {
+                    let (start, end) = (offset(start)?, offset(end)?);
+                    $Range::try_new(start, end).map_err(ParseRangeError::Invalid)?;
+                    (start, end.checked_add(1.into()).ok_or(ParseRangeError::Overflow)?)
+                } else if let Some((start, end)) = s.split_once("..") {
+                    (offset(start)?, offset(end)?)
+                } else if let Some((start, len)) = s.split_once('+') {
+                    let start = offset(start)?;
+                    let end = start.checked_add(offset(len)?);
+                    (start, end.ok_or(ParseRangeError::Overflow)?)
+                } else {
+                    let offset = offset(s)?;
+                    (offset, offset)
+                };
+                $Range::try_new(start, end).map_err(ParseRangeError::Invalid)
+            }
+        }
+
+        /// Identity methods.
+        impl $Range {
+            /// The start point of this range.
+            #[inline]
+            pub const fn start(self) -> $Size {
+                self.start
+            }
+
+            /// The end point of this range.
+            #[inline]
+            pub const fn end(self) -> $Size {
+                self.end
+            }
+
+            /// The size of this range.
+            #[inline]
+            pub const fn len(self) -> $Size {
+                // HACK for const fn: math on primitives only
+                $Size {
+                    raw: self.end().raw - self.start().raw,
+                }
+            }
+
+            /// Check if this range is empty.
+            #[inline]
+            pub const fn is_empty(self) -> bool {
+                // HACK for const fn: math on primitives only
+                self.start().raw == self.end().raw
+            }
+        }
+
+        /// Manipulation methods.
+        impl $Range {
+            /// Check if this range contains an offset.
+            ///
+            /// The end index is considered excluded.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            #[doc = concat!(" let (start, end): (", stringify!($Size), ", ", stringify!($Size), ");")]
+            /// # start = 10.into(); end = 20.into();
+            #[doc = concat!(" let range = ", stringify!($Range), "::new(start, end);")]
+            /// assert!(range.contains(start));
+            /// assert!(!range.contains(end));
+            /// ```
+            #[inline]
+            pub fn contains(self, offset: $Size) -> bool {
+                self.start() <= offset && offset < self.end()
+            }
+
+            /// Check if this range contains an offset.
+            ///
+            /// The end index is considered included.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            #[doc = concat!(" let (start, end): (", stringify!($Size), ", ", stringify!($Size), ");")]
+            /// # start = 10.into(); end = 20.into();
+            #[doc = concat!(" let range = ", stringify!($Range), "::new(start, end);")]
+            /// assert!(range.contains_inclusive(start));
+            /// assert!(range.contains_inclusive(end));
+            /// ```
+            #[inline]
+            pub fn contains_inclusive(self, offset: $Size) -> bool {
+                self.start() <= offset && offset <= self.end()
+            }
+
+            /// Check if this range completely contains another range.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            #[doc = concat!(" let larger = ", stringify!($Range), "::new(0.into(), 20.into());")]
+            #[doc = concat!(" let smaller = ", stringify!($Range), "::new(5.into(), 15.into());")]
+            /// assert!(larger.contains_range(smaller));
+            /// assert!(!smaller.contains_range(larger));
+            ///
+            /// // a range always contains itself
+            /// assert!(larger.contains_range(larger));
+            /// assert!(smaller.contains_range(smaller));
+            /// ```
+            #[inline]
+            pub fn contains_range(self, other: $Range) -> bool {
+                self.start() <= other.start() && other.end() <= self.end()
+            }
+
+            /// The range covered by both ranges, if it exists.
+            /// If the ranges touch but do not overlap, the output range is empty.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            /// assert_eq!(
+            #[doc = concat!("     ", stringify!($Range), "::intersect(")]
+            #[doc = concat!("         ", stringify!($Range), "::new(0.into(), 10.into()),")]
+            #[doc = concat!("         ", stringify!($Range), "::new(5.into(), 15.into()),")]
+            ///     ),
+            #[doc = concat!("     Some(", stringify!($Range), "::new(5.into(), 10.into())),")]
+            /// );
+            /// ```
+            #[inline]
+            pub fn intersect(self, other: $Range) -> Option<$Range> {
+                let start = std::cmp::max(self.start(), other.start());
+                let end = std::cmp::min(self.end(), other.end());
+                if end < start {
+                    return None;
+                }
+                Some($Range::new(start, end))
+            }
+
+            /// Extends the range to cover `other` as well.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            /// assert_eq!(
+            #[doc = concat!("     ", stringify!($Range), "::cover(")]
+            #[doc = concat!("         ", stringify!($Range), "::new(0.into(), 5.into()),")]
+            #[doc = concat!("         ", stringify!($Range), "::new(15.into(), 20.into()),")]
+            ///     ),
+            #[doc = concat!("     ", stringify!($Range), "::new(0.into(), 20.into()),")]
+            /// );
+            /// ```
+            #[inline]
+            pub fn cover(self, other: $Range) -> $Range {
+                let start = std::cmp::min(self.start(), other.start());
+                let end = std::cmp::max(self.end(), other.end());
+                $Range::new(start, end)
+            }
+
+            /// Extends the range to cover `other` offsets as well.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            /// assert_eq!(
+            #[doc = concat!("     ", stringify!($Range), "::empty(0.into()).cover_offset(20.into()),")]
+            #[doc = concat!("     ", stringify!($Range), "::new(0.into(), 20.into()),")]
+            /// )
+            /// ```
+            #[inline]
+            pub fn cover_offset(self, offset: $Size) -> $Range {
+                self.cover($Range::empty(offset))
+            }
+
+            /// The parts of this range before and after `other`.
+            ///
+            /// Each part is `None` if it is empty.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            /// assert_eq!(
+            #[doc = concat!("     ", stringify!($Range), "::subtract(")]
+            #[doc = concat!("         ", stringify!($Range), "::new(0.into(), 10.into()),")]
+            #[doc = concat!("         ", stringify!($Range), "::new(3.into(), 5.into()),")]
+            ///     ),
+            ///     (
+            #[doc = concat!("         Some(", stringify!($Range), "::new(0.into(), 3.into())),")]
+            #[doc = concat!("         Some(", stringify!($Range), "::new(5.into(), 10.into())),")]
+            ///     ),
+            /// );
+            /// assert_eq!(
+            #[doc = concat!("     ", stringify!($Range), "::subtract(")]
+            #[doc = concat!("         ", stringify!($Range), "::new(0.into(), 10.into()),")]
+            #[doc = concat!("         ", stringify!($Range), "::new(5.into(), 15.into()),")]
+            ///     ),
+            #[doc = concat!("     (Some(", stringify!($Range), "::new(0.into(), 5.into())), None),")]
+            /// );
+            /// ```
+            #[inline]
+            pub fn subtract(self, other: $Range) -> (Option<$Range>, Option<$Range>) {
+                let before_end = std::cmp::min(self.end(), other.start());
+                let after_start = std::cmp::max(self.start(), other.end());
+                let before = if self.start() < before_end {
+                    Some($Range::new(self.start(), before_end))
+                } else {
+                    None
+                };
+                let after = if after_start < self.end() {
+                    Some($Range::new(after_start, self.end()))
+                } else {
+                    None
+                };
+                (before, after)
+            }
+
+            /// Splits this range in two at `offset`.
+            ///
+            /// # Panics
+            ///
+            /// Panics if `offset` is not contained in this range, ends included.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            /// assert_eq!(
+            #[doc = concat!("     ", stringify!($Range), "::new(0.into(), 10.into()).split_at(4.into()),")]
+            #[doc = concat!("     (", stringify!($Range), "::new(0.into(), 4.into()), ", stringify!($Range), "::new(4.into(), 10.into())),")]
+            /// );
+            /// ```
+            #[inline]
+            pub fn split_at(self, offset: $Size) -> ($Range, $Range) {
+                assert!(
+                    self.contains_inclusive(offset),
+                    "offset {:?} is out of range {:?}",
+                    offset,
+                    self,
+                );
+                (
+                    $Range::new(self.start(), offset),
+                    $Range::new(offset, self.end()),
+                )
+            }
+
+            /// The range between two disjoint ranges, if they are disjoint.
+            /// If the ranges touch, the output range is empty.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            /// assert_eq!(
+            #[doc = concat!("     ", stringify!($Range), "::gap(")]
+            #[doc = concat!("         ", stringify!($Range), "::new(10.into(), 15.into()),")]
+            #[doc = concat!("         ", stringify!($Range), "::new(0.into(), 5.into()),")]
+            ///     ),
+            #[doc = concat!("     Some(", stringify!($Range), "::new(5.into(), 10.into())),")]
+            /// );
+            /// assert_eq!(
+            #[doc = concat!("     ", stringify!($Range), "::gap(")]
+            #[doc = concat!("         ", stringify!($Range), "::new(0.into(), 10.into()),")]
+            #[doc = concat!("         ", stringify!($Range), "::new(5.into(), 15.into()),")]
+            ///     ),
+            ///     None,
+            /// );
+            /// ```
+            #[inline]
+            pub fn gap(self, other: $Range) -> Option<$Range> {
+                let (first, second) = if (self.start(), self.end()) <= (other.start(), other.end())
+                {
+                    (self, other)
+                } else {
+                    (other, self)
+                };
+                if first.end() > second.start() {
+                    return None;
+                }
+                Some($Range::new(first.end(), second.start()))
+            }
+
+            /// The offset in this range closest to `offset`.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            #[doc = concat!(" let range = ", stringify!($Range), "::new(5.into(), 10.into());")]
+            #[doc = concat!(" assert_eq!(range.clamp(0.into()), ", stringify!($Size), "::from(5));")]
+            #[doc = concat!(" assert_eq!(range.clamp(7.into()), ", stringify!($Size), "::from(7));")]
+            #[doc = concat!(" assert_eq!(range.clamp(15.into()), ", stringify!($Size), "::from(10));")]
+            /// ```
+            #[inline]
+            pub fn clamp(self, offset: $Size) -> $Size {
+                std::cmp::min(std::cmp::max(offset, self.start()), self.end())
+            }
+
+            /// Add an offset to this range.
+            ///
+            #[doc = concat!(" Note that this is not appropriate for changing where a `", stringify!($Range), "` is")]
+            /// within some string; rather, it is for changing the reference anchor
+            #[doc = concat!(" that the `", stringify!($Range), "` is measured against.")]
+            $($(#[$note])*)?
+            ///
+            /// The unchecked version (`Add::add`) will _always_ panic on overflow,
+            /// in contrast to primitive integers, which check in debug mode only.
+            #[inline]
+            pub const fn checked_add(self, offset: $Size) -> Option<$Range> {
+                // HACK for const fn: no `?` operator
+                match (self.start.checked_add(offset), self.end.checked_add(offset)) {
+                    (Some(start), Some(end)) => Some($Range { start, end }),
+                    _ => None,
+                }
+            }
+
+            /// Subtract an offset from this range.
+            ///
+            #[doc = concat!(" Note that this is not appropriate for changing where a `", stringify!($Range), "` is")]
+            /// within some string; rather, it is for changing the reference anchor
+            #[doc = concat!(" that the `", stringify!($Range), "` is measured against.")]
+            $($(#[$note])*)?
+            ///
+            /// The unchecked version (`Sub::sub`) will _always_ panic on overflow,
+            /// in contrast to primitive integers, which check in debug mode only.
+            #[inline]
+            pub const fn checked_sub(self, offset: $Size) -> Option<$Range> {
+                // HACK for const fn: no `?` operator
+                match (self.start.checked_sub(offset), self.end.checked_sub(offset)) {
+                    (Some(start), Some(end)) => Some($Range { start, end }),
+                    _ => None,
+                }
+            }
+
+            /// Relative order of the two ranges (overlapping ranges are considered
+            /// equal).
+            ///
+            ///
+            /// This is useful when, for example, binary searching an array of disjoint
+            /// ranges.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// # use text_size::*;
+            /// # use std::cmp::Ordering;
+            ///
+            #[doc = concat!(" let a = ", stringify!($Range), "::new(0.into(), 3.into());")]
+            #[doc = concat!(" let b = ", stringify!($Range), "::new(4.into(), 5.into());")]
+            /// assert_eq!(a.ordering(b), Ordering::Less);
+            ///
+            #[doc = concat!(" let a = ", stringify!($Range), "::new(0.into(), 3.into());")]
+            #[doc = concat!(" let b = ", stringify!($Range), "::new(3.into(), 5.into());")]
+            /// assert_eq!(a.ordering(b), Ordering::Less);
+            ///
+            #[doc = concat!(" let a = ", stringify!($Range), "::new(0.into(), 3.into());")]
+            #[doc = concat!(" let b = ", stringify!($Range), "::new(2.into(), 5.into());")]
+            /// assert_eq!(a.ordering(b), Ordering::Equal);
+            ///
+            #[doc = concat!(" let a = ", stringify!($Range), "::new(0.into(), 3.into());")]
+            #[doc = concat!(" let b = ", stringify!($Range), "::new(2.into(), 2.into());")]
+            /// assert_eq!(a.ordering(b), Ordering::Equal);
+            ///
+            #[doc = concat!(" let a = ", stringify!($Range), "::new(2.into(), 3.into());")]
+            #[doc = concat!(" let b = ", stringify!($Range), "::new(2.into(), 2.into());")]
+            /// assert_eq!(a.ordering(b), Ordering::Greater);
+            /// ```
+            #[inline]
+            pub fn ordering(self, other: $Range) -> std::cmp::Ordering {
+                if self.end() <= other.start() {
+                    std::cmp::Ordering::Less
+                } else if other.end() <= self.start() {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            }
+        }
+
+        impl std::ops::RangeBounds<$Size> for $Range {
+            fn start_bound(&self) -> std::ops::Bound<&$Size> {
+                std::ops::Bound::Included(&self.start)
+            }
+
+            fn end_bound(&self) -> std::ops::Bound<&$Size> {
+                std::ops::Bound::Excluded(&self.end)
+            }
+        }
+
+        impl<T> From<$Range> for std::ops::Range<T>
+        where
+            T: From<$Size>,
+        {
+            #[inline]
+            fn from(r: $Range) -> Self {
+                r.start().into()..r.end().into()
+            }
+        }
+
+        impl std::ops::Add<$Size> for $Range {
+            type Output = $Range;
+            #[inline]
+            fn add(self, offset: $Size) -> $Range {
+                self.checked_add(offset)
+                    .expect(concat!(stringify!($Range), " +offset overflowed"))
+            }
+        }
+
+        impl std::ops::Sub<$Size> for $Range {
+            type Output = $Range;
+            #[inline]
+            fn sub(self, offset: $Size) -> $Range {
+                self.checked_sub(offset)
+                    .expect(concat!(stringify!($Range), " -offset overflowed"))
+            }
+        }
+
+        range_ops!(impl Add<$Size> for $Range by fn add = +);
+        range_ops!(impl Sub<$Size> for $Range by fn sub = -);
+
+        assign_ops!(impl AddAssign<A> for $Range by fn add_assign = + via Add);
+        assign_ops!(impl SubAssign<S> for $Range by fn sub_assign = - via Sub);
+    };
+}
+
+/// The UTF-8 specific API of a range: slicing text and bytes.
+macro_rules! text_range_impls {
+    ($Range:ident($Size:ident)) => {
+        /// Ranges which do not fit in `usize` can not be in bounds of any text.
+        #[inline]
+        fn index_range(range: $Range) -> std::ops::Range<usize> {
+            $crate::macros::usize_saturating(range.start().raw)
+                ..$crate::macros::usize_saturating(range.end().raw)
+        }
+
+        /// Slicing methods.
+        impl $Range {
+            /// The text in this range, if it is in bounds of `text` and on char
+            /// boundaries.
+            ///
+            /// Unlike indexing, this never panics.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            /// let text = "größe";
+            #[doc = concat!(" assert_eq!(", stringify!($Range), "::new(0.into(), 2.into()).get(text), Some(\"gr\"));")]
+            #[doc = concat!(" assert_eq!(", stringify!($Range), "::new(0.into(), 3.into()).get(text), None);")]
+            #[doc = concat!(" assert_eq!(", stringify!($Range), "::new(0.into(), 10.into()).get(text), None);")]
+            /// ```
+            #[inline]
+            pub fn get(self, text: &str) -> Option<&str> {
+                text.get(index_range(self))
+            }
+
+            /// The bytes in this range, if it is in bounds of `bytes`.
+            ///
+            /// Unlike indexing, this never panics.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            /// let bytes = b"fn main() {}";
+            #[doc = concat!(" assert_eq!(", stringify!($Range), "::new(3.into(), 7.into()).get_bytes(bytes), Some(&b\"main\"[..]));")]
+            #[doc = concat!(" assert_eq!(", stringify!($Range), "::new(3.into(), 20.into()).get_bytes(bytes), None);")]
+            /// ```
+            #[inline]
+            pub fn get_bytes(self, bytes: &[u8]) -> Option<&[u8]> {
+                bytes.get(index_range(self))
+            }
+
+            /// Check that this range can be used to slice `text`.
+            ///
+            /// # Examples
+            ///
+            /// ```rust
+            /// # use text_size::*;
+            /// let text = "größe";
+            #[doc = concat!(" let range = ", stringify!($Range), "::new(0.into(), 3.into());")]
+            /// assert_eq!(
+            ///     range.checked_for(text),
+            ///     Err(RangeError::NotCharBoundary { offset: 3.into() }),
+            /// );
+            /// ```
+            pub fn checked_for(
+                self,
+                text: &str,
+            ) -> Result<$Range, $crate::RangeError<$Range, $Size>> {
+                let range = index_range(self);
+                if range.end > text.len() {
+                    return Err($crate::RangeError::OutOfBounds {
+                        range: self,
+                        len: $Size::of(text),
+                    });
+                }
+                let ends = [(self.start(), range.start), (self.end(), range.end)];
+                match ends.iter().find(|&&(_, it)| !text.is_char_boundary(it)) {
+                    Some(&(offset, _)) => Err($crate::RangeError::NotCharBoundary { offset }),
+                    None => Ok(self),
+                }
+            }
+        }
+
+        index_impls!(impl Index<$Range> for str => str);
+        index_impls!(impl Index<$Range> for String => str);
+        index_impls!(impl Index<$Range> for Box<str> => str, by deref);
+        index_impls!(impl Index<$Range> for std::borrow::Cow<'_, str> => str, by deref);
+        index_impls!(impl Index<$Range> for std::rc::Rc<str> => str, by deref);
+        index_impls!(impl Index<$Range> for std::sync::Arc<str> => str, by deref);
+        index_impls!(impl Index<$Range> for [u8] => [u8]);
+        index_impls!(impl Index<$Range> for Vec<u8> => [u8]);
+
+        index_impls!(impl IndexMut<$Range> for str => str);
+        index_impls!(impl IndexMut<$Range> for String => str);
+        index_impls!(impl IndexMut<$Range> for Box<str> => str, by deref);
+        index_impls!(impl IndexMut<$Range> for [u8] => [u8]);
+        index_impls!(impl IndexMut<$Range> for Vec<u8> => [u8]);
+    };
+}
+
+macro_rules! size_ops {
+    (impl $Op:ident for $Size:ident by fn $f:ident = $op:tt) => {
+        impl std::ops::$Op<$Size> for $Size {
+            type Output = $Size;
+            #[inline]
+            fn $f(self, other: $Size) -> $Size {
+                $Size { raw: self.raw $op other.raw }
+            }
+        }
+        impl std::ops::$Op<&$Size> for $Size {
+            type Output = $Size;
+            #[inline]
+            fn $f(self, other: &$Size) -> $Size {
+                self $op *other
+            }
+        }
+        impl<T> std::ops::$Op<T> for &$Size
+        where
+            $Size: std::ops::$Op<T, Output=$Size>,
+        {
+            type Output = $Size;
+            #[inline]
+            fn $f(self, other: T) -> $Size {
+                *self $op other
+            }
+        }
+    };
+}
+
+macro_rules! scalar_ops {
+    (impl $Op:ident<$Int:ident> for $Size:ident by fn $f:ident = $op:tt) => {
+        impl std::ops::$Op<$Int> for $Size {
+            type Output = $Size;
+            #[inline]
+            fn $f(self, other: $Int) -> $Size {
+                $Size { raw: self.raw $op other }
+            }
+        }
+        impl std::ops::$Op<&$Int> for $Size {
+            type Output = $Size;
+            #[inline]
+            fn $f(self, other: &$Int) -> $Size {
+                self $op *other
+            }
+        }
+    };
+}
+
+macro_rules! range_ops {
+    (impl $Op:ident<$Size:ident> for $Range:ident by fn $f:ident = $op:tt) => {
+        impl std::ops::$Op<&$Size> for $Range {
+            type Output = $Range;
+            #[inline]
+            fn $f(self, other: &$Size) -> $Range {
+                self $op *other
+            }
+        }
+        impl<T> std::ops::$Op<T> for &$Range
+        where
+            $Range: std::ops::$Op<T, Output=$Range>,
+        {
+            type Output = $Range;
+            #[inline]
+            fn $f(self, other: T) -> $Range {
+                *self $op other
+            }
+        }
+    };
+}
+
+macro_rules! assign_ops {
+    (impl $OpAssign:ident<$R:ident> for $T:ident by fn $f:ident = $op:tt via $Op:ident) => {
+        impl<$R> std::ops::$OpAssign<$R> for $T
+        where
+            $T: std::ops::$Op<$R, Output = $T>,
+        {
+            #[inline]
+            fn $f(&mut self, rhs: $R) {
+                *self = *self $op rhs
+            }
+        }
+    };
+}
+
+macro_rules! index_impls {
+    (impl Index<$Range:ident> for $T:ty => $Output:ty) => {
+        impl std::ops::Index<$Range> for $T {
+            type Output = $Output;
+            #[inline]
+            fn index(&self, index: $Range) -> &$Output {
+                &self[index_range(index)]
+            }
+        }
+    };
+    (impl Index<$Range:ident> for $T:ty => $Output:ty, by deref) => {
+        impl std::ops::Index<$Range> for $T {
+            type Output = $Output;
+            #[inline]
+            fn index(&self, index: $Range) -> &$Output {
+                &(**self)[index]
+            }
+        }
+    };
+    (impl IndexMut<$Range:ident> for $T:ty => $Output:ty, by deref) => {
+        impl std::ops::IndexMut<$Range> for $T {
+            #[inline]
+            fn index_mut(&mut self, index: $Range) -> &mut $Output {
+                &mut (**self)[index]
+            }
+        }
+    };
+    (impl IndexMut<$Range:ident> for $T:ty => $Output:ty) => {
+        impl std::ops::IndexMut<$Range> for $T {
+            #[inline]
+            fn index_mut(&mut self, index: $Range) -> &mut $Output {
+                &mut self[index_range(index)]
+            }
+        }
+    };
+}
+
+/// Converts a raw offset to `usize`, saturating at `usize::MAX`, which is out
+/// of bounds of any text.
+#[inline]
+pub(crate) fn usize_saturating<T: std::convert::TryInto<usize>>(raw: T) -> usize {
+    raw.try_into().unwrap_or(usize::MAX)
+}
@@ -0,0 +1,218 @@
+use {
+    crate::{TextRange, TextSize},
+    std::{convert::TryFrom, error::Error, fmt},
+};
+
+/// An `Option<TextSize>` which is the same size as a [`TextSize`].
+///
+/// `u32::MAX` is reserved to mean `None`, so [`TextSize::MAX`] itself can
+/// not be stored.
+///
+/// # Examples
+///
+/// ```rust
+/// # use text_size::*;
+/// let offset = OptTextSize::some(92.into());
+/// assert_eq!(offset.get(), Some(TextSize::from(92)));
+/// assert_eq!(OptTextSize::NONE.get(), None);
+/// assert_eq!(std::mem::size_of::<OptTextSize>(), std::mem::size_of::<TextSize>());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OptTextSize {
+    // Invariant: `u32::MAX` means `None`.
+    raw: u32,
+}
+
+impl fmt::Debug for OptTextSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl Default for OptTextSize {
+    #[inline]
+    fn default() -> OptTextSize {
+        OptTextSize::NONE
+    }
+}
+
+impl OptTextSize {
+    /// No offset.
+    pub const NONE: OptTextSize = OptTextSize { raw: u32::MAX };
+
+    /// Some `size`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is [`TextSize::MAX`], which is reserved for `None`. See
+    /// [`checked_some`](OptTextSize::checked_some) for a version which does
+    /// not panic.
+    #[inline]
+    pub const fn some(size: TextSize) -> OptTextSize {
+        assert!(
+            size.raw != u32::MAX,
+            "TextSize::MAX can not be stored in OptTextSize"
+        );
+        OptTextSize { raw: size.raw }
+    }
+
+    /// Some `size`, or `None` if `size` is [`TextSize::MAX`], which is reserved
+    /// for `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// assert!(OptTextSize::checked_some(92.into()).is_some());
+    /// assert_eq!(OptTextSize::checked_some(TextSize::MAX), None);
+    /// ```
+    #[inline]
+    pub const fn checked_some(size: TextSize) -> Option<OptTextSize> {
+        if size.raw == u32::MAX {
+            return None;
+        }
+        Some(OptTextSize { raw: size.raw })
+    }
+
+    /// Converts to a plain `Option`.
+    #[inline]
+    pub const fn get(self) -> Option<TextSize> {
+        if self.is_none() {
+            return None;
+        }
+        Some(TextSize { raw: self.raw })
+    }
+
+    /// Check if this is `Some`.
+    #[inline]
+    pub const fn is_some(self) -> bool {
+        !self.is_none()
+    }
+
+    /// Check if this is `None`.
+    #[inline]
+    pub const fn is_none(self) -> bool {
+        self.raw == u32::MAX
+    }
+}
+
+impl TryFrom<Option<TextSize>> for OptTextSize {
+    type Error = ReservedSizeError;
+    #[inline]
+    fn try_from(value: Option<TextSize>) -> Result<Self, ReservedSizeError> {
+        match value {
+            Some(size) => OptTextSize::checked_some(size).ok_or(ReservedSizeError(())),
+            None => Ok(OptTextSize::NONE),
+        }
+    }
+}
+
+impl From<OptTextSize> for Option<TextSize> {
+    #[inline]
+    fn from(value: OptTextSize) -> Self {
+        value.get()
+    }
+}
+
+/// The error returned when converting `Some(TextSize::MAX)`, which is reserved
+/// for `None`, into an [`OptTextSize`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ReservedSizeError(());
+
+impl fmt::Display for ReservedSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TextSize::MAX can not be stored in OptTextSize")
+    }
+}
+
+impl Error for ReservedSizeError {}
+
+/// An `Option<TextRange>` which is the same size as a [`TextRange`].
+///
+/// `None` is stored as a range whose start is after its end, so every
+/// [`TextRange`] can be stored.
+///
+/// # Examples
+///
+/// ```rust
+/// # use text_size::*;
+/// let range = TextRange::new(5.into(), 10.into());
+/// assert_eq!(OptTextRange::some(range).get(), Some(range));
+/// assert_eq!(OptTextRange::NONE.get(), None);
+/// assert_eq!(std::mem::size_of::<OptTextRange>(), std::mem::size_of::<TextRange>());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OptTextRange {
+    // Invariant: either `start <= end`, or this is `NONE`.
+    start: TextSize,
+    end: TextSize,
+}
+
+impl fmt::Debug for OptTextRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+impl Default for OptTextRange {
+    #[inline]
+    fn default() -> OptTextRange {
+        OptTextRange::NONE
+    }
+}
+
+impl OptTextRange {
+    /// No range.
+    pub const NONE: OptTextRange = OptTextRange {
+        start: TextSize::MAX,
+        end: TextSize::ZERO,
+    };
+
+    /// Some `range`.
+    #[inline]
+    pub const fn some(range: TextRange) -> OptTextRange {
+        OptTextRange {
+            start: range.start(),
+            end: range.end(),
+        }
+    }
+
+    /// Converts to a plain `Option`.
+    #[inline]
+    pub const fn get(self) -> Option<TextRange> {
+        if self.is_none() {
+            return None;
+        }
+        Some(TextRange::new(self.start, self.end))
+    }
+
+    /// Check if this is `Some`.
+    #[inline]
+    pub const fn is_some(self) -> bool {
+        !self.is_none()
+    }
+
+    /// Check if this is `None`.
+    #[inline]
+    pub const fn is_none(self) -> bool {
+        // HACK for const fn: math on primitives only
+        self.start.raw > self.end.raw
+    }
+}
+
+impl From<Option<TextRange>> for OptTextRange {
+    #[inline]
+    fn from(value: Option<TextRange>) -> Self {
+        match value {
+            Some(range) => OptTextRange::some(range),
+            None => OptTextRange::NONE,
+        }
+    }
+}
+
+impl From<OptTextRange> for Option<TextRange> {
+    #[inline]
+    fn from(value: OptTextRange) -> Self {
+        value.get()
+    }
+}
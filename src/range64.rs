@@ -0,0 +1,63 @@
+use {
+    crate::{TextRange, TextSize64},
+    std::{convert::TryFrom, num::TryFromIntError, ops::Range},
+};
+
+/// A range in text, represented as a pair of [`TextSize64`].
+///
+/// This is the 64-bit counterpart of [`TextRange`], with the same API.
+/// Converting a `TextRange` into a `TextRange64` is lossless, the other
+/// direction is checked via [`TryFrom`].
+///
+/// It is a logic error for `start` to be greater than `end`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use text_size::*;
+/// # use std::convert::TryFrom;
+/// let range = TextRange::new(5.into(), 10.into());
+/// let wide = TextRange64::from(range);
+/// assert_eq!(TextRange::try_from(wide), Ok(range));
+///
+/// let huge = TextRange64::new(0.into(), (5 << 30).into());
+/// assert!(TextRange::try_from(huge).is_err());
+/// ```
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct TextRange64 {
+    // Invariant: start <= end
+    start: TextSize64,
+    end: TextSize64,
+}
+
+range_impls!(TextRange64(TextSize64), at_example: "0123456789" => "23456");
+text_range_impls!(TextRange64(TextSize64));
+
+impl From<TextRange> for TextRange64 {
+    #[inline]
+    fn from(range: TextRange) -> Self {
+        TextRange64 {
+            start: range.start().into(),
+            end: range.end().into(),
+        }
+    }
+}
+
+impl TryFrom<TextRange64> for TextRange {
+    type Error = TryFromIntError;
+    #[inline]
+    fn try_from(range: TextRange64) -> Result<Self, TryFromIntError> {
+        Ok(TextRange::new(
+            TryFrom::try_from(range.start())?,
+            TryFrom::try_from(range.end())?,
+        ))
+    }
+}
+
+impl TryFrom<TextRange64> for Range<usize> {
+    type Error = TryFromIntError;
+    #[inline]
+    fn try_from(range: TextRange64) -> Result<Self, TryFromIntError> {
+        Ok(usize::try_from(range.start())?..usize::try_from(range.end())?)
+    }
+}
@@ -0,0 +1,244 @@
+use {
+    crate::{TextRange, TextSize},
+    std::{cmp, fmt, iter},
+};
+
+/// A map from possibly overlapping [`TextRange`]s to values, which supports
+/// finding all entries that overlap a range or contain an offset.
+///
+/// Several entries may have the same range.
+///
+/// Entries are stored in a vector sorted by range, which doubles as an
+/// implicit balanced search tree: every subtree also remembers the largest
+/// end among its ranges. Queries take `O(log n + k)` time for `k` results.
+/// [`insert`](TextRangeMap::insert) and [`remove`](TextRangeMap::remove) take
+/// `O(n)` time, so prefer collecting many entries at once from an iterator.
+///
+/// # Examples
+///
+/// ```rust
+/// # use text_size::*;
+/// let range = |start: u32, end: u32| TextRange::new(start.into(), end.into());
+///
+/// let diagnostics: TextRangeMap<&str> = vec![
+///     (range(0, 10), "unused function"),
+///     (range(4, 5), "unknown type"),
+///     (range(30, 40), "missing semicolon"),
+/// ]
+/// .into_iter()
+/// .collect();
+///
+/// let visible: Vec<_> = diagnostics.overlapping(range(3, 20)).map(|(_, it)| *it).collect();
+/// assert_eq!(visible, vec!["unused function", "unknown type"]);
+///
+/// let at_cursor: Vec<_> = diagnostics.containing(8.into()).map(|(_, it)| *it).collect();
+/// assert_eq!(at_cursor, vec!["unused function"]);
+/// ```
+#[derive(Clone)]
+pub struct TextRangeMap<V> {
+    // Invariant: sorted by `(start, end)`, entries with equal ranges in the
+    // order they were inserted.
+    entries: Vec<(TextRange, V)>,
+    // Invariant: `max_end[mid]` is the largest end in the subtree rooted at
+    // `mid`, see `build`.
+    max_end: Vec<TextSize>,
+}
+
+impl<V> Default for TextRangeMap<V> {
+    fn default() -> TextRangeMap<V> {
+        TextRangeMap {
+            entries: Vec::new(),
+            max_end: Vec::new(),
+        }
+    }
+}
+
+impl<V: fmt::Debug> fmt::Debug for TextRangeMap<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<V> TextRangeMap<V> {
+    /// Creates an empty map.
+    #[inline]
+    pub fn new() -> TextRangeMap<V> {
+        TextRangeMap::default()
+    }
+
+    /// The number of entries in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the map has no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over all entries, sorted by range.
+    pub fn iter(&self) -> impl Iterator<Item = (TextRange, &V)> + '_ {
+        self.entries.iter().map(|(range, value)| (*range, value))
+    }
+
+    /// Adds an entry, keeping any existing entries with the same range.
+    pub fn insert(&mut self, range: TextRange, value: V) {
+        let key = (range.start(), range.end());
+        let i = self
+            .entries
+            .partition_point(|(it, _)| (it.start(), it.end()) <= key);
+        self.entries.insert(i, (range, value));
+        self.rebuild();
+    }
+
+    /// Removes the earliest inserted entry with exactly this range, and
+    /// returns its value.
+    pub fn remove(&mut self, range: TextRange) -> Option<V> {
+        let key = (range.start(), range.end());
+        let i = self
+            .entries
+            .partition_point(|(it, _)| (it.start(), it.end()) < key);
+        if self.entries.get(i)?.0 != range {
+            return None;
+        }
+        let (_, value) = self.entries.remove(i);
+        self.rebuild();
+        Some(value)
+    }
+
+    /// Keeps only the entries for which `f` returns `true`.
+    pub fn retain(&mut self, mut f: impl FnMut(TextRange, &mut V) -> bool) {
+        self.entries.retain_mut(|(range, value)| f(*range, value));
+        self.rebuild();
+    }
+
+    /// Iterates over the entries whose range intersects `range`, sorted by
+    /// range.
+    ///
+    /// As with [`TextRange::intersect`], ranges which only touch `range` are
+    /// included.
+    pub fn overlapping(&self, range: TextRange) -> Query<'_, V> {
+        Query::new(self, range.end(), Some(range.start()))
+    }
+
+    /// Iterates over the entries whose range contains `offset`, sorted by
+    /// range.
+    ///
+    /// As with [`TextRange::contains`], range ends are excluded.
+    pub fn containing(&self, offset: TextSize) -> Query<'_, V> {
+        Query::new(self, offset, offset.checked_add(1.into()))
+    }
+
+    fn rebuild(&mut self) {
+        self.max_end.clear();
+        self.max_end.resize(self.entries.len(), TextSize::from(0));
+        build(&self.entries, &mut self.max_end, 0, self.entries.len());
+    }
+}
+
+/// Fills `max_end` for the subtree of `entries[lo..hi]`, rooted at its middle
+/// element, and returns the largest end in it.
+fn build<V>(
+    entries: &[(TextRange, V)],
+    max_end: &mut [TextSize],
+    lo: usize,
+    hi: usize,
+) -> TextSize {
+    if lo >= hi {
+        return TextSize::from(0);
+    }
+    let mid = lo + (hi - lo) / 2;
+    let left = build(entries, max_end, lo, mid);
+    let right = build(entries, max_end, mid + 1, hi);
+    let res = cmp::max(entries[mid].0.end(), cmp::max(left, right));
+    max_end[mid] = res;
+    res
+}
+
+/// An iterator over the entries of a [`TextRangeMap`] matching a query.
+///
+/// Created by [`TextRangeMap::overlapping`] and [`TextRangeMap::containing`].
+#[derive(Debug)]
+pub struct Query<'a, V> {
+    map: &'a TextRangeMap<V>,
+    // Entries match if `start <= max_start` and `end >= min_end`.
+    max_start: TextSize,
+    min_end: TextSize,
+    // Pending steps of an in-order traversal, the next one on top.
+    stack: Vec<Step>,
+}
+
+#[derive(Debug)]
+enum Step {
+    /// The subtree of `entries[lo..hi]`.
+    Subtree(usize, usize),
+    /// A single entry.
+    Entry(usize),
+}
+
+impl<'a, V> Query<'a, V> {
+    fn new(
+        map: &'a TextRangeMap<V>,
+        max_start: TextSize,
+        min_end: Option<TextSize>,
+    ) -> Query<'a, V> {
+        let stack = match min_end {
+            Some(_) => vec![Step::Subtree(0, map.entries.len())],
+            None => Vec::new(),
+        };
+        Query {
+            map,
+            max_start,
+            min_end: min_end.unwrap_or_default(),
+            stack,
+        }
+    }
+}
+
+impl<'a, V> Iterator for Query<'a, V> {
+    type Item = (TextRange, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(step) = self.stack.pop() {
+            let (lo, hi) = match step {
+                Step::Entry(i) => {
+                    let (range, value) = &self.map.entries[i];
+                    return Some((*range, value));
+                }
+                Step::Subtree(lo, hi) if lo < hi => (lo, hi),
+                Step::Subtree(..) => continue,
+            };
+            let mid = lo + (hi - lo) / 2;
+            if self.map.max_end[mid] < self.min_end {
+                continue;
+            }
+            let range = self.map.entries[mid].0;
+            if range.start() <= self.max_start {
+                self.stack.push(Step::Subtree(mid + 1, hi));
+                if range.end() >= self.min_end {
+                    self.stack.push(Step::Entry(mid));
+                }
+            }
+            self.stack.push(Step::Subtree(lo, mid));
+        }
+        None
+    }
+}
+
+impl<V> Extend<(TextRange, V)> for TextRangeMap<V> {
+    fn extend<I: IntoIterator<Item = (TextRange, V)>>(&mut self, iter: I) {
+        self.entries.extend(iter);
+        self.entries.sort_by_key(|(it, _)| (it.start(), it.end()));
+        self.rebuild();
+    }
+}
+
+impl<V> iter::FromIterator<(TextRange, V)> for TextRangeMap<V> {
+    fn from_iter<I: IntoIterator<Item = (TextRange, V)>>(iter: I) -> TextRangeMap<V> {
+        let mut res = TextRangeMap::new();
+        res.extend(iter);
+        res
+    }
+}
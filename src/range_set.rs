@@ -0,0 +1,226 @@
+use {
+    crate::{TextRange, TextSize},
+    std::{cmp, fmt, iter, slice, vec},
+};
+
+/// A set of offsets, stored as sorted, disjoint [`TextRange`]s.
+///
+/// Ranges are coalesced: overlapping or touching ranges are merged, and empty
+/// ranges are dropped, so two sets containing the same offsets are equal.
+///
+/// # Examples
+///
+/// ```rust
+/// # use text_size::*;
+/// let range = |start: u32, end: u32| TextRange::new(start.into(), end.into());
+///
+/// let comments: TextRangeSet = vec![range(0, 10), range(20, 30)].into_iter().collect();
+/// let selection: TextRangeSet = vec![range(5, 25)].into_iter().collect();
+///
+/// let unselected = comments.difference(&selection);
+/// assert_eq!(unselected.iter().collect::<Vec<_>>(), vec![range(0, 5), range(25, 30)]);
+/// ```
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct TextRangeSet {
+    // Invariant: sorted, non-empty, and with gaps between neighbours.
+    ranges: Vec<TextRange>,
+}
+
+impl fmt::Debug for TextRangeSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(&self.ranges).finish()
+    }
+}
+
+impl TextRangeSet {
+    /// Creates an empty set.
+    #[inline]
+    pub fn new() -> TextRangeSet {
+        TextRangeSet::default()
+    }
+
+    /// Builds a set from ranges sorted by start, coalescing them.
+    fn from_sorted(ranges: impl Iterator<Item = TextRange>) -> TextRangeSet {
+        let mut res = TextRangeSet::new();
+        for range in ranges.filter(|it| !it.is_empty()) {
+            match res.ranges.last_mut() {
+                Some(last) if range.start() <= last.end() => *last = last.cover(range),
+                _ => res.ranges.push(range),
+            }
+        }
+        res
+    }
+
+    /// The number of disjoint ranges in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Check if the set contains no offsets.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The disjoint ranges of the set, in order.
+    #[inline]
+    pub fn as_slice(&self) -> &[TextRange] {
+        &self.ranges
+    }
+
+    /// Iterates over the disjoint ranges of the set, in order.
+    #[inline]
+    pub fn iter(&self) -> iter::Copied<slice::Iter<'_, TextRange>> {
+        self.ranges.iter().copied()
+    }
+
+    /// Check if some range of the set contains `offset`.
+    ///
+    /// As with [`TextRange::contains`], range ends are excluded.
+    pub fn contains(&self, offset: TextSize) -> bool {
+        let i = self.ranges.partition_point(|it| it.end() <= offset);
+        self.ranges.get(i).is_some_and(|it| it.contains(offset))
+    }
+
+    /// Adds the offsets of `range` to the set.
+    pub fn insert(&mut self, range: TextRange) {
+        if range.is_empty() {
+            return;
+        }
+        let lo = self.ranges.partition_point(|it| it.end() < range.start());
+        let hi = self.ranges.partition_point(|it| it.start() <= range.end());
+        let merged = self.ranges[lo..hi]
+            .iter()
+            .fold(range, |acc, &it| acc.cover(it));
+        self.ranges.splice(lo..hi, iter::once(merged));
+    }
+
+    /// Removes the offsets of `range` from the set.
+    pub fn remove(&mut self, range: TextRange) {
+        if range.is_empty() {
+            return;
+        }
+        let lo = self.ranges.partition_point(|it| it.end() <= range.start());
+        let hi = self.ranges.partition_point(|it| it.start() < range.end());
+        if lo == hi {
+            return;
+        }
+        let first = self.ranges[lo];
+        let last = self.ranges[hi - 1];
+        let left = TextRange::new(first.start(), cmp::max(first.start(), range.start()));
+        let right = TextRange::new(cmp::min(last.end(), range.end()), last.end());
+        let rest = [left, right];
+        let rest = rest.iter().copied().filter(|it| !it.is_empty());
+        self.ranges.splice(lo..hi, rest);
+    }
+
+    /// The offsets in either set.
+    pub fn union(&self, other: &TextRangeSet) -> TextRangeSet {
+        let mut a = self.iter().peekable();
+        let mut b = other.iter().peekable();
+        let merged = iter::from_fn(|| match (a.peek(), b.peek()) {
+            (Some(x), Some(y)) if y.start() < x.start() => b.next(),
+            (Some(_), _) => a.next(),
+            (None, _) => b.next(),
+        });
+        TextRangeSet::from_sorted(merged)
+    }
+
+    /// The offsets in both sets.
+    pub fn intersection(&self, other: &TextRangeSet) -> TextRangeSet {
+        let mut res = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(&a), Some(&b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            if let Some(it) = a.intersect(b).filter(|it| !it.is_empty()) {
+                res.push(it);
+            }
+            if a.end() < b.end() {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        TextRangeSet { ranges: res }
+    }
+
+    /// The offsets in this set, but not in `other`.
+    pub fn difference(&self, other: &TextRangeSet) -> TextRangeSet {
+        let mut res = Vec::new();
+        let mut j = 0;
+        for &range in &self.ranges {
+            let mut start = range.start();
+            while let Some(&b) = other.ranges.get(j) {
+                if b.start() >= range.end() {
+                    break;
+                }
+                if b.end() > start {
+                    if b.start() > start {
+                        res.push(TextRange::new(start, b.start()));
+                    }
+                    start = b.end();
+                }
+                if b.end() > range.end() {
+                    break;
+                }
+                j += 1;
+            }
+            if start < range.end() {
+                res.push(TextRange::new(start, range.end()));
+            }
+        }
+        TextRangeSet { ranges: res }
+    }
+
+    /// The offsets of `range` which are not in this set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let range = |start: u32, end: u32| TextRange::new(start.into(), end.into());
+    ///
+    /// let set: TextRangeSet = vec![range(2, 4), range(6, 8)].into_iter().collect();
+    /// let gaps = set.complement_within(range(0, 7));
+    /// assert_eq!(gaps.iter().collect::<Vec<_>>(), vec![range(0, 2), range(4, 6)]);
+    /// ```
+    pub fn complement_within(&self, range: TextRange) -> TextRangeSet {
+        iter::once(range).collect::<TextRangeSet>().difference(self)
+    }
+}
+
+impl Extend<TextRange> for TextRangeSet {
+    fn extend<I: IntoIterator<Item = TextRange>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range)
+        }
+    }
+}
+
+impl iter::FromIterator<TextRange> for TextRangeSet {
+    fn from_iter<I: IntoIterator<Item = TextRange>>(iter: I) -> TextRangeSet {
+        let mut ranges: Vec<TextRange> = iter.into_iter().collect();
+        ranges.sort_by_key(|it| it.start());
+        TextRangeSet::from_sorted(ranges.into_iter())
+    }
+}
+
+impl IntoIterator for TextRangeSet {
+    type Item = TextRange;
+    type IntoIter = vec::IntoIter<TextRange>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.ranges.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a TextRangeSet {
+    type Item = TextRange;
+    type IntoIter = iter::Copied<slice::Iter<'a, TextRange>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
@@ -0,0 +1,183 @@
+use {
+    crate::{TextRange, TextSize},
+    rkyv::{
+        bytecheck::CheckBytes,
+        rancor::{Fallible, Source},
+        traits::NoUndef,
+        Archive, Archived, Deserialize, Place, Portable, Serialize,
+    },
+    std::fmt,
+};
+
+/// An archived [`TextSize`], as stored by `rkyv`.
+///
+/// This is a little-endian `u32`, which can be read in place with
+/// [`ArchivedTextSize::to_native`].
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct ArchivedTextSize {
+    raw: Archived<u32>,
+}
+
+impl fmt::Debug for ArchivedTextSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.to_native().fmt(f)
+    }
+}
+
+impl ArchivedTextSize {
+    /// The [`TextSize`] stored in this archive.
+    #[inline]
+    pub fn to_native(self) -> TextSize {
+        self.raw.to_native().into()
+    }
+}
+
+impl From<TextSize> for ArchivedTextSize {
+    #[inline]
+    fn from(size: TextSize) -> ArchivedTextSize {
+        ArchivedTextSize {
+            raw: Archived::<u32>::from_native(size.raw),
+        }
+    }
+}
+
+// SAFETY: `ArchivedTextSize` is a `repr(transparent)` wrapper around a
+// portable little-endian `u32`, for which every bit pattern is valid.
+unsafe impl Portable for ArchivedTextSize {}
+unsafe impl NoUndef for ArchivedTextSize {}
+
+unsafe impl<C> CheckBytes<C> for ArchivedTextSize
+where
+    C: Fallible + ?Sized,
+{
+    #[inline]
+    unsafe fn check_bytes(_: *const Self, _: &mut C) -> Result<(), C::Error> {
+        Ok(())
+    }
+}
+
+impl Archive for TextSize {
+    type Archived = ArchivedTextSize;
+    type Resolver = ();
+
+    #[inline]
+    fn resolve(&self, (): (), out: Place<ArchivedTextSize>) {
+        out.write(ArchivedTextSize::from(*self));
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for TextSize {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<(), S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<TextSize, D> for ArchivedTextSize {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<TextSize, D::Error> {
+        Ok(self.to_native())
+    }
+}
+
+/// An archived [`TextRange`], as stored by `rkyv`.
+///
+/// Validating an archive, for example with `rkyv::access`, rejects ranges
+/// whose start is after their end with an [`InvalidRange`](crate::InvalidRange)
+/// error, so a validated range can be read in place with
+/// [`ArchivedTextRange::to_native`].
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct ArchivedTextRange {
+    // Invariant: start <= end, checked by `CheckBytes`
+    start: ArchivedTextSize,
+    end: ArchivedTextSize,
+}
+
+impl fmt::Debug for ArchivedTextRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}..{:?}", self.start, self.end)
+    }
+}
+
+impl ArchivedTextRange {
+    /// The start point of this range.
+    #[inline]
+    pub fn start(self) -> TextSize {
+        self.start.to_native()
+    }
+
+    /// The end point of this range.
+    #[inline]
+    pub fn end(self) -> TextSize {
+        self.end.to_native()
+    }
+
+    /// The [`TextRange`] stored in this archive.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the archive was accessed without validation and the start of
+    /// the range is after its end.
+    #[inline]
+    pub fn to_native(self) -> TextRange {
+        TextRange::new(self.start(), self.end())
+    }
+}
+
+impl From<TextRange> for ArchivedTextRange {
+    #[inline]
+    fn from(range: TextRange) -> ArchivedTextRange {
+        ArchivedTextRange {
+            start: range.start().into(),
+            end: range.end().into(),
+        }
+    }
+}
+
+// SAFETY: `ArchivedTextRange` is a `repr(C)` pair of `ArchivedTextSize`s, so
+// it is portable and has no padding.
+unsafe impl Portable for ArchivedTextRange {}
+unsafe impl NoUndef for ArchivedTextRange {}
+
+// SAFETY: every bit pattern of the fields is valid, so only the `start <= end`
+// invariant needs to be checked.
+unsafe impl<C> CheckBytes<C> for ArchivedTextRange
+where
+    C: Fallible + ?Sized,
+    C::Error: Source,
+{
+    #[inline]
+    unsafe fn check_bytes(value: *const Self, _: &mut C) -> Result<(), C::Error> {
+        // SAFETY: the caller guarantees that `value` is aligned and points to
+        // enough initialized bytes.
+        let range = unsafe { *value };
+        TextRange::try_new(range.start(), range.end()).map_err(Source::new)?;
+        Ok(())
+    }
+}
+
+impl Archive for TextRange {
+    type Archived = ArchivedTextRange;
+    type Resolver = ();
+
+    #[inline]
+    fn resolve(&self, (): (), out: Place<ArchivedTextRange>) {
+        out.write(ArchivedTextRange::from(*self));
+    }
+}
+
+impl<S: Fallible + ?Sized> Serialize<S> for TextRange {
+    #[inline]
+    fn serialize(&self, _: &mut S) -> Result<(), S::Error> {
+        Ok(())
+    }
+}
+
+impl<D: Fallible + ?Sized> Deserialize<TextRange, D> for ArchivedTextRange {
+    #[inline]
+    fn deserialize(&self, _: &mut D) -> Result<TextRange, D::Error> {
+        Ok(self.to_native())
+    }
+}
@@ -1,6 +1,10 @@
 use {
     crate::{TextRange, TextSize},
-    serde::{de, Deserialize, Deserializer, Serialize, Serializer},
+    serde::{
+        de::{self, Visitor},
+        Deserialize, Deserializer, Serialize, Serializer,
+    },
+    std::{fmt, marker::PhantomData, str::FromStr},
 };
 
 impl Serialize for TextSize {
@@ -31,18 +35,315 @@ impl Serialize for TextRange {
 }
 
 impl<'de> Deserialize<'de> for TextRange {
-    #[allow(clippy::nonminimal_bool)]
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         let (start, end) = Deserialize::deserialize(deserializer)?;
-        if !(start <= end) {
-            return Err(de::Error::custom(format!(
-                "invalid range: {:?}..{:?}",
-                start, end
-            )));
+        TextRange::try_new(start, end).map_err(de::Error::custom)
+    }
+}
+
+/// Deserializes any [`FromStr`] type from a string.
+struct FromStrVisitor<T> {
+    expecting: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<T> FromStrVisitor<T> {
+    fn new(expecting: &'static str) -> FromStrVisitor<T> {
+        FromStrVisitor {
+            expecting,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> Visitor<'de> for FromStrVisitor<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<T, E>
+    where
+        E: de::Error,
+    {
+        v.parse().map_err(E::custom)
+    }
+}
+
+/// Alternative representations of [`TextSize`].
+pub mod text_size {
+    /// Represents a [`TextSize`](crate::TextSize) as a string of its decimal
+    /// value, such as `"92"`, for formats which can not hold large numbers
+    /// precisely.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// #[derive(serde::Serialize, serde::Deserialize)]
+    /// struct Cursor {
+    ///     #[serde(with = "text_size::serde_helpers::text_size::as_string")]
+    ///     offset: TextSize,
+    /// }
+    /// ```
+    pub mod as_string {
+        use {
+            super::super::FromStrVisitor,
+            crate::TextSize,
+            serde::{Deserializer, Serializer},
+        };
+
+        /// Serializes `size` as a string.
+        pub fn serialize<S>(size: &TextSize, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_str(size)
+        }
+
+        /// Deserializes a [`TextSize`] from a string.
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<TextSize, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_str(FromStrVisitor::new("a text size string"))
+        }
+    }
+}
+
+/// Alternative representations of [`TextRange`].
+///
+/// By default, a [`TextRange`] is represented as a `(start, end)` tuple.
+pub mod text_range {
+    /// Represents a [`TextRange`](crate::TextRange) as a
+    /// `{ "start": _, "end": _ }` struct.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// #[derive(serde::Serialize, serde::Deserialize)]
+    /// struct Diagnostic {
+    ///     #[serde(with = "text_size::serde_helpers::text_range::as_struct")]
+    ///     range: TextRange,
+    ///     message: String,
+    /// }
+    /// ```
+    pub mod as_struct {
+        use {
+            crate::{TextRange, TextSize},
+            serde::{
+                de::{self, IgnoredAny, MapAccess, SeqAccess, Visitor},
+                ser::SerializeStruct,
+                Deserialize, Deserializer, Serializer,
+            },
+            std::fmt,
+        };
+
+        const FIELDS: &[&str] = &["start", "end"];
+
+        /// Serializes `range` as a struct.
+        pub fn serialize<S>(range: &TextRange, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let mut state = serializer.serialize_struct("TextRange", 2)?;
+            state.serialize_field("start", &range.start())?;
+            state.serialize_field("end", &range.end())?;
+            state.end()
+        }
+
+        /// Deserializes a [`TextRange`] from a struct.
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<TextRange, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_struct("TextRange", FIELDS, RangeVisitor)
+        }
+
+        enum Field {
+            Start,
+            End,
+            Other,
+        }
+
+        impl<'de> Deserialize<'de> for Field {
+            fn deserialize<D>(deserializer: D) -> Result<Field, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_identifier(FieldVisitor)
+            }
+        }
+
+        struct FieldVisitor;
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a field identifier")
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Field, E>
+            where
+                E: de::Error,
+            {
+                Ok(match v {
+                    0 => Field::Start,
+                    1 => Field::End,
+                    _ => Field::Other,
+                })
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Field, E>
+            where
+                E: de::Error,
+            {
+                Ok(match v {
+                    "start" => Field::Start,
+                    "end" => Field::End,
+                    _ => Field::Other,
+                })
+            }
+        }
+
+        struct RangeVisitor;
+
+        impl<'de> Visitor<'de> for RangeVisitor {
+            type Value = TextRange;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("struct TextRange")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<TextRange, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                let start = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+                let end = seq
+                    .next_element()?
+                    .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+                TextRange::try_new(start, end).map_err(de::Error::custom)
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<TextRange, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut start: Option<TextSize> = None;
+                let mut end: Option<TextSize> = None;
+                while let Some(field) = map.next_key()? {
+                    match field {
+                        Field::Start if start.is_some() => {
+                            return Err(de::Error::duplicate_field("start"))
+                        }
+                        Field::End if end.is_some() => {
+                            return Err(de::Error::duplicate_field("end"))
+                        }
+                        Field::Start => start = Some(map.next_value()?),
+                        Field::End => end = Some(map.next_value()?),
+                        Field::Other => {
+                            map.next_value::<IgnoredAny>()?;
+                        }
+                    }
+                }
+                let start = start.ok_or_else(|| de::Error::missing_field("start"))?;
+                let end = end.ok_or_else(|| de::Error::missing_field("end"))?;
+                TextRange::try_new(start, end).map_err(de::Error::custom)
+            }
+        }
+    }
+
+    /// Represents a [`TextRange`](crate::TextRange) as a `(start, len)` tuple.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// #[derive(serde::Serialize, serde::Deserialize)]
+    /// struct Token {
+    ///     kind: u16,
+    ///     #[serde(with = "text_size::serde_helpers::text_range::as_start_len")]
+    ///     range: TextRange,
+    /// }
+    /// ```
+    pub mod as_start_len {
+        use {
+            crate::{TextRange, TextSize},
+            serde::{de, Deserialize, Deserializer, Serialize, Serializer},
+        };
+
+        /// Serializes `range` as a `(start, len)` tuple.
+        pub fn serialize<S>(range: &TextRange, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            (range.start(), range.len()).serialize(serializer)
+        }
+
+        /// Deserializes a [`TextRange`] from a `(start, len)` tuple.
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<TextRange, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let (start, len): (TextSize, TextSize) = Deserialize::deserialize(deserializer)?;
+            let end = start
+                .checked_add(len)
+                .ok_or_else(|| de::Error::custom("text range end overflowed"))?;
+            Ok(TextRange::new(start, end))
+        }
+    }
+
+    /// Represents a [`TextRange`](crate::TextRange) as a string, such as
+    /// `"1..5"`.
+    ///
+    /// Deserialization accepts every syntax supported by the
+    /// [`FromStr`](std::str::FromStr) implementation of
+    /// [`TextRange`](crate::TextRange).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// #[derive(serde::Serialize, serde::Deserialize)]
+    /// struct Fold {
+    ///     #[serde(with = "text_size::serde_helpers::text_range::as_string")]
+    ///     range: TextRange,
+    /// }
+    /// ```
+    pub mod as_string {
+        use {
+            super::super::FromStrVisitor,
+            crate::TextRange,
+            serde::{Deserializer, Serializer},
+        };
+
+        /// Serializes `range` as a string.
+        pub fn serialize<S>(range: &TextRange, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.collect_str(range)
+        }
+
+        /// Deserializes a [`TextRange`] from a string.
+        pub fn deserialize<'de, D>(deserializer: D) -> Result<TextRange, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_str(FromStrVisitor::new("a text range string"))
         }
-        Ok(TextRange::new(start, end))
     }
 }
@@ -0,0 +1,55 @@
+use {
+    crate::TextSize,
+    std::{convert::TryFrom, num::TryFromIntError},
+};
+
+/// A 64-bit measure of text length, for texts which may be larger than 4 GiB.
+///
+/// This is a UTF-8 bytes offset stored as `u64`, with the same API as
+/// [`TextSize`]. Converting a `TextSize` into a `TextSize64` is lossless, the
+/// other direction is checked via [`TryFrom`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use text_size::*;
+/// # use std::convert::TryFrom;
+/// let small = TextSize::from(92);
+/// let wide = TextSize64::from(small);
+/// assert_eq!(TextSize::try_from(wide), Ok(small));
+///
+/// let huge = TextSize64::from(5 << 30);
+/// assert!(TextSize::try_from(huge).is_err());
+/// ```
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TextSize64 {
+    pub(crate) raw: u64,
+}
+
+size_impls!(TextSize64(u64), "UTF-8 bytes");
+text_size_impls!(TextSize64, text_len64);
+
+impl TryFrom<TextSize64> for usize {
+    type Error = TryFromIntError;
+    #[inline]
+    fn try_from(value: TextSize64) -> Result<Self, TryFromIntError> {
+        usize::try_from(value.raw)
+    }
+}
+
+impl From<TextSize> for TextSize64 {
+    #[inline]
+    fn from(value: TextSize) -> Self {
+        TextSize64 {
+            raw: u64::from(value.raw),
+        }
+    }
+}
+
+impl TryFrom<TextSize64> for TextSize {
+    type Error = TryFromIntError;
+    #[inline]
+    fn try_from(value: TextSize64) -> Result<Self, TryFromIntError> {
+        Ok(u32::try_from(value.raw)?.into())
+    }
+}
@@ -0,0 +1,567 @@
+use {
+    crate::{LineIndex, LineTerminators, TextRange, TextSize},
+    std::{
+        cmp,
+        fmt::{self, Write},
+    },
+};
+
+/// A rustc-style rendering of labeled ranges of some source text.
+///
+/// Every snippet has one primary label, underlined with `^`, and any number
+/// of secondary labels, underlined with `-`. Ranges spanning several lines are
+/// drawn with a gutter on the left. Tabs are expanded to the next tab stop.
+/// Wide and zero-width characters are measured approximately, as the
+/// standard library has no Unicode width tables.
+///
+/// The snippet is rendered by its [`Display`](fmt::Display) implementation,
+/// without a trailing newline.
+///
+/// # Examples
+///
+/// ```rust
+/// # use text_size::*;
+/// let text = "fn main() {\n    let x = foo(bar);\n}\n";
+/// let mut snippet = Snippet::new(text, TextRange::at(28.into(), 3.into()), "not found");
+/// snippet.label(TextRange::at(24.into(), 3.into()), "in this call");
+/// snippet.origin("src/main.rs");
+///
+/// assert_eq!(
+///     snippet.to_string(),
+///     " --> src/main.rs:2:17
+///   |
+/// 2 |     let x = foo(bar);
+///   |             --- ^^^ not found
+///   |             |
+///   |             in this call",
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Snippet<'a> {
+    text: &'a str,
+    origin: Option<String>,
+    // The first label is the primary one.
+    labels: Vec<Label>,
+    tab_width: u32,
+    color: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Label {
+    range: TextRange,
+    message: String,
+    primary: bool,
+}
+
+impl<'a> Snippet<'a> {
+    /// A snippet of `text`, with a primary label on `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of `text` or is not on char
+    /// boundaries.
+    pub fn new(text: &'a str, range: TextRange, message: impl Into<String>) -> Snippet<'a> {
+        let mut res = Snippet {
+            text,
+            origin: None,
+            labels: Vec::new(),
+            tab_width: 4,
+            color: false,
+        };
+        res.push_label(range, message.into(), true);
+        res
+    }
+
+    /// Adds a secondary label on `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of the text or is not on char
+    /// boundaries.
+    pub fn label(&mut self, range: TextRange, message: impl Into<String>) {
+        self.push_label(range, message.into(), false)
+    }
+
+    /// Shows `origin`, usually a file name, and the position of the primary
+    /// label above the snippet.
+    pub fn origin(&mut self, origin: impl Into<String>) {
+        self.origin = Some(origin.into())
+    }
+
+    /// Sets the distance between tab stops, 4 by default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `tab_width` is zero.
+    pub fn tab_width(&mut self, tab_width: u32) {
+        assert!(tab_width > 0, "tab width must not be zero");
+        self.tab_width = tab_width
+    }
+
+    /// Sets whether to color the output with ANSI escape codes, off by
+    /// default.
+    pub fn color(&mut self, color: bool) {
+        self.color = color
+    }
+
+    fn push_label(&mut self, range: TextRange, message: String, primary: bool) {
+        if let Err(err) = range.checked_for(self.text) {
+            panic!("invalid label range: {}", err);
+        }
+        self.labels.push(Label {
+            range,
+            message,
+            primary,
+        })
+    }
+}
+
+impl fmt::Display for Snippet<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = Layout::new(self).rows();
+        for (i, row) in rows.iter().enumerate() {
+            if i > 0 {
+                f.write_char('\n')?;
+            }
+            row.write(f, self.color)?;
+        }
+        Ok(())
+    }
+}
+
+/// A label, resolved to display columns.
+struct Span<'s> {
+    label: &'s Label,
+    start_line: u32,
+    start_col: usize,
+    end_line: u32,
+    // Exclusive, so that every span covers at least one column.
+    end_col: usize,
+    // Multi-line spans starting in the indentation of their first line are
+    // drawn with a `/` next to that line, instead of a separate row.
+    slash: bool,
+}
+
+impl Span<'_> {
+    fn is_multiline(&self) -> bool {
+        self.start_line != self.end_line
+    }
+
+    fn style(&self) -> Style {
+        if self.label.primary {
+            Style::Primary
+        } else {
+            Style::Secondary
+        }
+    }
+
+    fn marker(&self) -> char {
+        if self.label.primary {
+            '^'
+        } else {
+            '-'
+        }
+    }
+}
+
+struct Layout<'s> {
+    snippet: &'s Snippet<'s>,
+    index: LineIndex,
+    spans: Vec<Span<'s>>,
+    // Indices of multi-line spans, one per gutter slot.
+    slots: Vec<usize>,
+    // Width of the line number column.
+    number_width: usize,
+}
+
+impl<'s> Layout<'s> {
+    fn new(snippet: &'s Snippet<'s>) -> Layout<'s> {
+        let index = LineIndex::with_terminators(snippet.text, LineTerminators::LfCrLf);
+        let mut res = Layout {
+            snippet,
+            index,
+            spans: Vec::new(),
+            slots: Vec::new(),
+            number_width: 0,
+        };
+        let spans: Vec<Span<'_>> = snippet.labels.iter().map(|it| res.span(it)).collect();
+        let mut slots: Vec<usize> = (0..spans.len())
+            .filter(|&i| spans[i].is_multiline())
+            .collect();
+        slots.sort_by_key(|&i| (spans[i].start_line, spans[i].start_col));
+        res.spans = spans;
+        res.slots = slots;
+        let last_line = res.spans.iter().map(|it| it.end_line).max().unwrap_or(0);
+        res.number_width = (last_line + 1).to_string().len();
+        res
+    }
+
+    fn line_text(&self, line: u32) -> &'s str {
+        let range = self.index.line_range(line).unwrap();
+        &self.snippet.text[range]
+    }
+
+    /// The line of `offset`, and its byte column, clamped to the end of the
+    /// line's text.
+    fn position(&self, offset: TextSize) -> (u32, usize) {
+        let line_col = self.index.line_col(offset);
+        let line = self.line_text(line_col.line);
+        (line_col.line, cmp::min(line_col.col as usize, line.len()))
+    }
+
+    fn span(&self, label: &'s Label) -> Span<'s> {
+        let range = label.range;
+        let (start_line, start_byte) = self.position(range.start());
+        let start_col = self.width(&self.line_text(start_line)[..start_byte]);
+        let (end_line, end_col) = if range.is_empty() {
+            (start_line, start_col + 1)
+        } else {
+            let last = (range.end() - TextSize::from(1)).floor_char_boundary(self.snippet.text);
+            let (line, byte) = self.position(last);
+            let text = self.line_text(line);
+            match text[byte..].chars().next() {
+                Some(c) => (line, self.width(&text[..byte + c.len_utf8()])),
+                // The last char is a line terminator, point just past the text.
+                None => (line, self.width(text) + 1),
+            }
+        };
+        let end_col = if start_line == end_line {
+            cmp::max(end_col, start_col + 1)
+        } else {
+            end_col
+        };
+        let indent = self.line_text(start_line);
+        let indent = self.width(&indent[..indent.len() - indent.trim_start().len()]);
+        Span {
+            label,
+            start_line,
+            start_col,
+            end_line,
+            end_col,
+            slash: start_line != end_line && start_col <= indent,
+        }
+    }
+
+    /// The display width of `text`, with tabs expanded.
+    fn width(&self, text: &str) -> usize {
+        text.chars().fold(0, |col, c| col + self.char_width(c, col))
+    }
+
+    fn char_width(&self, c: char, col: usize) -> usize {
+        if c == '\t' {
+            let tab_width = self.snippet.tab_width as usize;
+            return tab_width - col % tab_width;
+        }
+        char_width(c)
+    }
+
+    /// The lines to show, with `None` for elided lines.
+    fn lines(&self) -> Vec<Option<u32>> {
+        let mut interesting: Vec<u32> = Vec::new();
+        for span in &self.spans {
+            interesting.push(span.start_line);
+            interesting.push(span.end_line);
+        }
+        interesting.sort_unstable();
+        interesting.dedup();
+
+        let mut res = Vec::new();
+        for (i, &line) in interesting.iter().enumerate() {
+            if let Some(&prev) = i.checked_sub(1).map(|i| &interesting[i]) {
+                if line - prev > 3 {
+                    res.push(None);
+                } else {
+                    res.extend((prev + 1..line).map(Some));
+                }
+            }
+            res.push(Some(line));
+        }
+        res
+    }
+
+    fn slot_col(&self, slot: usize) -> usize {
+        self.number_width + 3 + 2 * slot
+    }
+
+    fn text_col(&self) -> usize {
+        self.slot_col(self.slots.len())
+    }
+
+    /// An empty row, with the line number column.
+    fn gutter(&self, line: Option<u32>) -> Row {
+        let mut row = Row::default();
+        if let Some(line) = line {
+            let number = (line + 1).to_string();
+            row.put_str(self.number_width - number.len(), &number, Style::Gutter);
+        }
+        row.put(self.number_width + 1, '|', Style::Gutter);
+        row
+    }
+
+    /// An empty row, with the line number column and a `|` for every
+    /// multi-line span that `is_open`.
+    fn gutter_with_bars(&self, is_open: impl Fn(usize, &Span<'_>) -> bool) -> Row {
+        let mut row = self.gutter(None);
+        for (slot, &i) in self.slots.iter().enumerate() {
+            let span = &self.spans[i];
+            if is_open(slot, span) {
+                row.put(self.slot_col(slot), '|', span.style());
+            }
+        }
+        row
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        let mut rows = Vec::new();
+        if let Some(origin) = &self.snippet.origin {
+            let (line, byte) = self.position(self.snippet.labels[0].range.start());
+            let col = self.line_text(line)[..byte].chars().count();
+            let mut row = Row::default();
+            row.put_str(self.number_width, "-->", Style::Gutter);
+            let location = format!("{}:{}:{}", origin, line + 1, col + 1);
+            row.put_str(self.number_width + 4, &location, Style::Plain);
+            rows.push(row);
+        }
+        rows.push(self.gutter(None));
+
+        for line in self.lines() {
+            let line = match line {
+                Some(it) => it,
+                None => {
+                    let mut row = Row::default();
+                    row.put_str(0, "...", Style::Gutter);
+                    rows.push(row);
+                    continue;
+                }
+            };
+            self.source_row(line, &mut rows);
+            self.single_line_rows(line, &mut rows);
+            self.end_rows(line, &mut rows);
+            self.start_rows(line, &mut rows);
+        }
+        rows
+    }
+
+    fn source_row(&self, line: u32, rows: &mut Vec<Row>) {
+        let mut row = self.gutter(Some(line));
+        for (slot, &i) in self.slots.iter().enumerate() {
+            let span = &self.spans[i];
+            if span.start_line < line && line <= span.end_line {
+                row.put(self.slot_col(slot), '|', span.style());
+            } else if span.start_line == line && span.slash {
+                row.put(self.slot_col(slot), '/', span.style());
+            }
+        }
+        let mut text = String::new();
+        for c in self.line_text(line).trim_end().chars() {
+            match c {
+                '\t' => (0..self.char_width(c, self.width(&text))).for_each(|_| text.push(' ')),
+                // Like rustc, show other control chars, such as a lone `\r`,
+                // as their Unicode Control Pictures, so they can not mess up
+                // the terminal.
+                '\u{0}'..='\u{1f}' => text.push(char::from_u32(0x2400 + c as u32).unwrap()),
+                '\u{7f}' => text.push('\u{2421}'),
+                _ => text.push(c),
+            }
+        }
+        row.push_text(self.text_col(), text);
+        rows.push(row);
+    }
+
+    /// Underlines for the single-line spans on `line`, followed by their
+    /// messages: inline for the rightmost span if there is room, and on
+    /// separate rows connected by `|` otherwise.
+    fn single_line_rows(&self, line: u32, rows: &mut Vec<Row>) {
+        let mut spans: Vec<&Span<'_>> = self
+            .spans
+            .iter()
+            .filter(|it| !it.is_multiline() && it.start_line == line)
+            .collect();
+        if spans.is_empty() {
+            return;
+        }
+        spans.sort_by_key(|it| it.start_col);
+        let is_open = |_: usize, span: &Span<'_>| {
+            (span.start_line < line || (span.start_line == line && span.slash))
+                && line <= span.end_line
+        };
+        let text_col = self.text_col();
+
+        let mut row = self.gutter_with_bars(is_open);
+        // The primary underline goes last, so that it wins on overlaps.
+        let secondary = spans.iter().filter(|it| !it.label.primary);
+        for span in secondary.chain(spans.iter().filter(|it| it.label.primary)) {
+            for col in span.start_col..span.end_col {
+                row.put(text_col + col, span.marker(), span.style());
+            }
+        }
+        let last = spans[spans.len() - 1];
+        let inline = !last.label.message.is_empty()
+            && spans[..spans.len() - 1]
+                .iter()
+                .all(|it| it.end_col <= last.start_col);
+        if inline {
+            row.put_str(
+                text_col + last.end_col + 1,
+                &last.label.message,
+                last.style(),
+            );
+            spans.pop();
+        }
+        rows.push(row);
+
+        spans.retain(|it| !it.label.message.is_empty());
+        if spans.is_empty() {
+            return;
+        }
+        let mut row = self.gutter_with_bars(is_open);
+        for span in &spans {
+            row.put(text_col + span.start_col, '|', span.style());
+        }
+        rows.push(row);
+        while let Some(span) = spans.pop() {
+            let mut row = self.gutter_with_bars(is_open);
+            for other in &spans {
+                row.put(text_col + other.start_col, '|', other.style());
+            }
+            row.put_str(text_col + span.start_col, &span.label.message, span.style());
+            rows.push(row);
+        }
+    }
+
+    /// The ends of multi-line spans ending on `line`, innermost first.
+    fn end_rows(&self, line: u32, rows: &mut Vec<Row>) {
+        for (slot, &i) in self.slots.iter().enumerate().rev() {
+            let span = &self.spans[i];
+            if span.end_line != line {
+                continue;
+            }
+            // Spans in earlier slots started no later than this one.
+            let mut row = self
+                .gutter_with_bars(|other_slot, other| other_slot < slot && line <= other.end_line);
+            // A span ending in a zero-width char at the start of a line has
+            // an `end_col` of 0, keep the marker out of the gutter.
+            let marker_col = self.text_col() + cmp::max(span.end_col, 1) - 1;
+            row.put(self.slot_col(slot), '|', span.style());
+            for col in self.slot_col(slot) + 1..marker_col {
+                row.put(col, '_', span.style());
+            }
+            row.put(marker_col, span.marker(), span.style());
+            if !span.label.message.is_empty() {
+                row.put_str(marker_col + 2, &span.label.message, span.style());
+            }
+            rows.push(row);
+        }
+    }
+
+    /// The starts of multi-line spans starting on `line`, outermost first.
+    fn start_rows(&self, line: u32, rows: &mut Vec<Row>) {
+        for (slot, &i) in self.slots.iter().enumerate() {
+            let span = &self.spans[i];
+            if span.start_line != line || span.slash {
+                continue;
+            }
+            // Spans in earlier slots started no later than this one, and those
+            // ending on this line are already closed.
+            let mut row = self
+                .gutter_with_bars(|other_slot, other| other_slot < slot && line < other.end_line);
+            let marker_col = self.text_col() + span.start_col;
+            for col in self.slot_col(slot) + 1..marker_col {
+                row.put(col, '_', span.style());
+            }
+            row.put(marker_col, span.marker(), span.style());
+            rows.push(row);
+        }
+    }
+}
+
+/// Approximate display width of `c` in a terminal.
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        // Combining marks, zero-width spaces and joiners, variation selectors.
+        0x0300..=0x036F | 0x200B..=0x200F | 0xFE00..=0xFE0F => 0,
+        // Hangul, CJK, fullwidth forms and emoji.
+        0x1100..=0x115F
+        | 0x2E80..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F300..=0x1F64F
+        | 0x1F900..=0x1F9FF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Plain,
+    Gutter,
+    Primary,
+    Secondary,
+}
+
+impl Style {
+    fn ansi(self) -> &'static str {
+        match self {
+            Style::Plain => "\x1b[0m",
+            Style::Gutter | Style::Secondary => "\x1b[1;34m",
+            Style::Primary => "\x1b[1;31m",
+        }
+    }
+}
+
+/// A row of styled cells, one per display column, optionally followed by
+/// unstyled text.
+#[derive(Default)]
+struct Row {
+    cells: Vec<(char, Style)>,
+    // Source text, which may contain wide or zero-width chars, so it can not
+    // be split into cells.
+    text: String,
+}
+
+impl Row {
+    fn put(&mut self, col: usize, c: char, style: Style) {
+        if self.cells.len() <= col {
+            self.cells.resize(col + 1, (' ', Style::Plain));
+        }
+        self.cells[col] = (c, style);
+    }
+
+    fn put_str(&mut self, col: usize, text: &str, style: Style) {
+        for (i, c) in text.chars().enumerate() {
+            self.put(col + i, c, style);
+        }
+    }
+
+    fn push_text(&mut self, col: usize, text: String) {
+        self.cells.resize(col, (' ', Style::Plain));
+        self.text = text;
+    }
+
+    fn write(&self, f: &mut fmt::Formatter<'_>, color: bool) -> fmt::Result {
+        let len = match self.text.is_empty() {
+            true => self
+                .cells
+                .iter()
+                .rposition(|&(c, _)| c != ' ')
+                .map_or(0, |it| it + 1),
+            false => self.cells.len(),
+        };
+        let mut current = Style::Plain;
+        for &(c, style) in &self.cells[..len] {
+            if color && style != current && c != ' ' {
+                f.write_str(style.ansi())?;
+                current = style;
+            }
+            f.write_char(c)?;
+        }
+        if color && current != Style::Plain {
+            f.write_str(Style::Plain.ansi())?;
+        }
+        f.write_str(&self.text)
+    }
+}
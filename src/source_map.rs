@@ -0,0 +1,191 @@
+use {
+    crate::{TextRange, TextSize},
+    std::convert::TryFrom,
+};
+
+/// Identifies a file in a [`SourceMap`].
+///
+/// This is the index of the file in its map. It is not tied to that map, so
+/// using it with another map is not detected: it refers to the file with the
+/// same index there, if there is one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FileId(u32);
+
+/// A set of files laid out one after another in a single global offset space.
+///
+/// Each file is assigned a contiguous global [`TextRange`]. Each file starts
+/// one offset after the end of the previous one, so that the end of each file,
+/// including an empty one, has a global offset of its own.
+///
+/// # Examples
+///
+/// ```rust
+/// # use text_size::*;
+/// let mut map = SourceMap::new();
+/// let main = map.add_file(TextSize::of("fn main() {}"));
+/// let lib = map.add_file(TextSize::of("pub fn f() {}"));
+///
+/// assert_eq!(map.file_range(lib), Some(TextRange::new(13.into(), 26.into())));
+/// assert_eq!(map.lookup(16.into()), Some((lib, 3.into())));
+/// assert_eq!(map.to_global(main, 3.into()), Some(3.into()));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SourceMap {
+    // Invariant: each range starts one offset after the end of the previous.
+    files: Vec<TextRange>,
+}
+
+impl SourceMap {
+    /// Creates an empty map.
+    #[inline]
+    pub fn new() -> SourceMap {
+        SourceMap::default()
+    }
+
+    /// The number of files in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Check if the map has no files.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Adds a file of length `len` after all existing files, and returns its
+    /// id.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the global offset space would overflow.
+    pub fn add_file(&mut self, len: TextSize) -> FileId {
+        let start = match self.files.last() {
+            Some(last) => last.end().checked_add(1.into()),
+            None => Some(0.into()),
+        };
+        let range = start
+            .and_then(|start| Some(TextRange::new(start, start.checked_add(len)?)))
+            .expect("SourceMap offset space overflowed");
+        let id = u32::try_from(self.files.len()).expect("too many files in SourceMap");
+        self.files.push(range);
+        FileId(id)
+    }
+
+    /// The global range of `file`.
+    ///
+    /// Returns `None` if there is no `file` in this map.
+    #[inline]
+    pub fn file_range(&self, file: FileId) -> Option<TextRange> {
+        self.files.get(file.0 as usize).copied()
+    }
+
+    /// Iterates over the files and their global ranges, in order.
+    pub fn iter(&self) -> impl Iterator<Item = (FileId, TextRange)> + '_ {
+        let files = self.files.iter().enumerate();
+        files.map(|(i, &range)| (FileId(i as u32), range))
+    }
+
+    /// The file containing the global `offset`, and the offset within it.
+    ///
+    /// The end of a file counts as part of it. Returns `None` if `offset` is
+    /// past the end of the last file.
+    pub fn lookup(&self, offset: TextSize) -> Option<(FileId, TextSize)> {
+        let i = self.files.partition_point(|it| it.end() < offset);
+        let range = self.files.get(i)?;
+        Some((FileId(i as u32), offset - range.start()))
+    }
+
+    /// The global offset of `offset` within `file`.
+    ///
+    /// Returns `None` if `offset` is past the end of `file`, or if there is no
+    /// `file` in this map.
+    pub fn to_global(&self, file: FileId, offset: TextSize) -> Option<TextSize> {
+        let range = self.files.get(file.0 as usize)?;
+        if offset > range.len() {
+            return None;
+        }
+        Some(range.start() + offset)
+    }
+
+    /// The file containing the global `range`, and the range within it.
+    ///
+    /// Returns `None` if `range` does not lie within a single file.
+    pub fn lookup_range(&self, range: TextRange) -> Option<FileRange> {
+        let (file, start) = self.lookup(range.start())?;
+        if range.end() > self.files[file.0 as usize].end() {
+            return None;
+        }
+        Some(FileRange::new(file, TextRange::at(start, range.len())))
+    }
+
+    /// The global range of `range`.
+    ///
+    /// Returns `None` if `range` ends past the end of its file, or if there is
+    /// no such file in this map.
+    pub fn to_global_range(&self, range: FileRange) -> Option<TextRange> {
+        let start = self.to_global(range.file, range.range.start())?;
+        let end = self.to_global(range.file, range.range.end())?;
+        Some(TextRange::new(start, end))
+    }
+}
+
+/// A [`TextRange`] within a specific file.
+///
+/// Operations combining two ranges refuse ranges from different files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FileRange {
+    /// The file.
+    pub file: FileId,
+    /// The range, relative to the start of `file`.
+    pub range: TextRange,
+}
+
+impl FileRange {
+    /// A range within `file`.
+    #[inline]
+    pub fn new(file: FileId, range: TextRange) -> FileRange {
+        FileRange { file, range }
+    }
+
+    /// Check if this range contains `offset` of `file`.
+    ///
+    /// The end index is considered excluded.
+    #[inline]
+    pub fn contains(self, file: FileId, offset: TextSize) -> bool {
+        self.file == file && self.range.contains(offset)
+    }
+
+    /// Check if this range completely contains another range.
+    ///
+    /// Ranges in different files never contain each other.
+    #[inline]
+    pub fn contains_range(self, other: FileRange) -> bool {
+        self.file == other.file && self.range.contains_range(other.range)
+    }
+
+    /// The range covered by both ranges, if it exists.
+    /// If the ranges touch but do not overlap, the output range is empty.
+    ///
+    /// Returns `None` if the ranges are in different files.
+    #[inline]
+    pub fn intersect(self, other: FileRange) -> Option<FileRange> {
+        if self.file != other.file {
+            return None;
+        }
+        let range = self.range.intersect(other.range)?;
+        Some(FileRange::new(self.file, range))
+    }
+
+    /// Extends the range to cover `other` as well.
+    ///
+    /// Returns `None` if the ranges are in different files.
+    #[inline]
+    pub fn cover(self, other: FileRange) -> Option<FileRange> {
+        if self.file != other.file {
+            return None;
+        }
+        Some(FileRange::new(self.file, self.range.cover(other.range)))
+    }
+}
@@ -0,0 +1,514 @@
+use {
+    crate::{InvalidRange, RangeError, TextRange, TextSize},
+    std::{
+        borrow::Cow,
+        cmp::Ordering,
+        fmt,
+        hash::{Hash, Hasher},
+        marker::PhantomData,
+        ops::{Add, AddAssign, Index, IndexMut, Sub, SubAssign},
+        rc::Rc,
+        sync::Arc,
+    },
+};
+
+/// A coordinate space that offsets can be measured in, see [`Tagged`].
+pub trait Space {}
+
+/// Offsets measured from the start of the whole text, such as a file.
+#[derive(Debug)]
+pub enum Absolute {}
+
+impl Space for Absolute {}
+
+/// Offsets measured from the start of some enclosing range, such as a syntax
+/// node.
+#[derive(Debug)]
+pub enum Relative {}
+
+impl Space for Relative {}
+
+/// A [`TextSize`] or [`TextRange`] tagged with the [`Space`] it is measured
+/// in.
+///
+/// Offsets from different spaces can not be mixed: converting between spaces
+/// requires an explicit [`relative_to`](Tagged::relative_to) or
+/// [`absolute_from`](Tagged::absolute_from).
+///
+/// # Examples
+///
+/// ```rust
+/// # use text_size::*;
+/// let node: Tagged<TextRange> = Tagged::new(TextRange::new(10.into(), 20.into()));
+/// let token: Tagged<TextRange> = Tagged::new(TextRange::new(12.into(), 15.into()));
+///
+/// let in_node: Tagged<TextRange, Relative> = token.relative_to(node).unwrap();
+/// assert_eq!(in_node.value(), TextRange::new(2.into(), 5.into()));
+/// assert_eq!(in_node.absolute_from(node), Some(token));
+/// ```
+///
+/// Ranges from different spaces can not be compared or combined:
+///
+/// ```compile_fail
+/// # use text_size::*;
+/// let absolute: Tagged<TextRange> = Tagged::new(TextRange::new(10.into(), 20.into()));
+/// let relative: Tagged<TextRange, Relative> = Tagged::new(TextRange::new(2.into(), 5.into()));
+/// absolute.cover(relative);
+/// ```
+///
+/// ```compile_fail
+/// # use text_size::*;
+/// let absolute: Tagged<TextSize> = Tagged::new(10.into());
+/// let relative: Tagged<TextSize, Relative> = Tagged::new(2.into());
+/// absolute < relative;
+/// ```
+///
+/// Only absolute ranges can be made relative:
+///
+/// ```compile_fail
+/// # use text_size::*;
+/// let parent: Tagged<TextRange, Relative> = Tagged::new(TextRange::new(10.into(), 20.into()));
+/// let child: Tagged<TextRange, Relative> = Tagged::new(TextRange::new(12.into(), 15.into()));
+/// child.relative_to(parent);
+/// ```
+///
+/// Only absolute ranges can index the text:
+///
+/// ```compile_fail
+/// # use text_size::*;
+/// let relative: Tagged<TextRange, Relative> = Tagged::new(TextRange::new(2.into(), 5.into()));
+/// let _ = &"hello world"[relative];
+/// ```
+pub struct Tagged<T, S: Space = Absolute> {
+    value: T,
+    space: PhantomData<fn() -> S>,
+}
+
+impl<T, S: Space> Tagged<T, S> {
+    /// Tags `value` as being measured in the space `S`.
+    #[inline]
+    pub fn new(value: T) -> Tagged<T, S> {
+        Tagged {
+            value,
+            space: PhantomData,
+        }
+    }
+
+    /// The untagged value.
+    #[inline]
+    pub fn value(self) -> T {
+        self.value
+    }
+}
+
+/// Conversions between spaces.
+impl Tagged<TextSize, Absolute> {
+    /// This offset, measured from the start of `parent` instead.
+    ///
+    /// Returns `None` if `parent` does not contain this offset, ends
+    /// included.
+    #[inline]
+    pub fn relative_to(
+        self,
+        parent: Tagged<TextRange, Absolute>,
+    ) -> Option<Tagged<TextSize, Relative>> {
+        if !parent.value.contains_inclusive(self.value) {
+            return None;
+        }
+        Some(Tagged::new(self.value - parent.value.start()))
+    }
+}
+
+impl Tagged<TextSize, Relative> {
+    /// This offset, measured from the start of the whole text instead, given
+    /// the `parent` it is relative to.
+    ///
+    /// Returns `None` if this offset is past the end of `parent`.
+    #[inline]
+    pub fn absolute_from(
+        self,
+        parent: Tagged<TextRange, Absolute>,
+    ) -> Option<Tagged<TextSize, Absolute>> {
+        if self.value > parent.value.len() {
+            return None;
+        }
+        Some(Tagged::new(parent.value.start() + self.value))
+    }
+}
+
+impl Tagged<TextRange, Absolute> {
+    /// This range, measured from the start of `parent` instead.
+    ///
+    /// Returns `None` if `parent` does not contain this range.
+    #[inline]
+    pub fn relative_to(
+        self,
+        parent: Tagged<TextRange, Absolute>,
+    ) -> Option<Tagged<TextRange, Relative>> {
+        if !parent.value.contains_range(self.value) {
+            return None;
+        }
+        Some(Tagged::new(self.value - parent.value.start()))
+    }
+}
+
+impl Tagged<TextRange, Relative> {
+    /// This range, measured from the start of the whole text instead, given
+    /// the `parent` it is relative to.
+    ///
+    /// Returns `None` if this range ends past the end of `parent`.
+    #[inline]
+    pub fn absolute_from(
+        self,
+        parent: Tagged<TextRange, Absolute>,
+    ) -> Option<Tagged<TextRange, Absolute>> {
+        if self.value.end() > parent.value.len() {
+            return None;
+        }
+        Some(Tagged::new(self.value + parent.value.start()))
+    }
+}
+
+/// Methods mirroring those of [`TextSize`], within a single space.
+impl<S: Space> Tagged<TextSize, S> {
+    /// Checked addition. Returns `None` if overflow occurred.
+    #[inline]
+    pub fn checked_add(self, rhs: TextSize) -> Option<Tagged<TextSize, S>> {
+        self.value.checked_add(rhs).map(Tagged::new)
+    }
+
+    /// Checked subtraction. Returns `None` if overflow occurred.
+    #[inline]
+    pub fn checked_sub(self, rhs: TextSize) -> Option<Tagged<TextSize, S>> {
+        self.value.checked_sub(rhs).map(Tagged::new)
+    }
+}
+
+/// Methods mirroring those of [`TextRange`], within a single space.
+impl<S: Space> Tagged<TextRange, S> {
+    /// Creates a new range with the given `start` and `end` (`start..end`).
+    ///
+    /// Returns an error if `end < start`.
+    #[inline]
+    pub fn try_new(
+        start: Tagged<TextSize, S>,
+        end: Tagged<TextSize, S>,
+    ) -> Result<Tagged<TextRange, S>, InvalidRange> {
+        TextRange::try_new(start.value, end.value).map(Tagged::new)
+    }
+
+    /// Create a new range with the given `offset` and `len`
+    /// (`offset..offset + len`).
+    #[inline]
+    pub fn at(offset: Tagged<TextSize, S>, len: TextSize) -> Tagged<TextRange, S> {
+        Tagged::new(TextRange::at(offset.value, len))
+    }
+
+    /// Create a zero-length range at the specified offset (`offset..offset`).
+    #[inline]
+    pub fn empty(offset: Tagged<TextSize, S>) -> Tagged<TextRange, S> {
+        Tagged::new(TextRange::empty(offset.value))
+    }
+
+    /// Create a range up to the given end (`..end`).
+    #[inline]
+    pub fn up_to(end: Tagged<TextSize, S>) -> Tagged<TextRange, S> {
+        Tagged::new(TextRange::up_to(end.value))
+    }
+
+    /// The start point of this range.
+    #[inline]
+    pub fn start(self) -> Tagged<TextSize, S> {
+        Tagged::new(self.value.start())
+    }
+
+    /// The end point of this range.
+    #[inline]
+    pub fn end(self) -> Tagged<TextSize, S> {
+        Tagged::new(self.value.end())
+    }
+
+    /// The size of this range.
+    ///
+    /// Sizes are the same in every space, so this is not tagged.
+    #[inline]
+    pub fn len(self) -> TextSize {
+        self.value.len()
+    }
+
+    /// Check if this range is empty.
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// Check if this range contains an offset.
+    ///
+    /// The end index is considered excluded.
+    #[inline]
+    pub fn contains(self, offset: Tagged<TextSize, S>) -> bool {
+        self.value.contains(offset.value)
+    }
+
+    /// Check if this range contains an offset.
+    ///
+    /// The end index is considered included.
+    #[inline]
+    pub fn contains_inclusive(self, offset: Tagged<TextSize, S>) -> bool {
+        self.value.contains_inclusive(offset.value)
+    }
+
+    /// Check if this range completely contains another range.
+    #[inline]
+    pub fn contains_range(self, other: Tagged<TextRange, S>) -> bool {
+        self.value.contains_range(other.value)
+    }
+
+    /// The range covered by both ranges, if it exists.
+    /// If the ranges touch but do not overlap, the output range is empty.
+    #[inline]
+    pub fn intersect(self, other: Tagged<TextRange, S>) -> Option<Tagged<TextRange, S>> {
+        self.value.intersect(other.value).map(Tagged::new)
+    }
+
+    /// Extends the range to cover `other` as well.
+    #[inline]
+    pub fn cover(self, other: Tagged<TextRange, S>) -> Tagged<TextRange, S> {
+        Tagged::new(self.value.cover(other.value))
+    }
+
+    /// Extends the range to cover `other` offsets as well.
+    #[inline]
+    pub fn cover_offset(self, offset: Tagged<TextSize, S>) -> Tagged<TextRange, S> {
+        Tagged::new(self.value.cover_offset(offset.value))
+    }
+
+    /// The parts of this range before and after `other`.
+    ///
+    /// Each part is `None` if it is empty.
+    #[inline]
+    pub fn subtract(self, other: Tagged<TextRange, S>) -> (Option<Self>, Option<Self>) {
+        let (before, after) = self.value.subtract(other.value);
+        (before.map(Tagged::new), after.map(Tagged::new))
+    }
+
+    /// Splits this range in two at `offset`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` is not contained in this range, ends included.
+    #[inline]
+    pub fn split_at(
+        self,
+        offset: Tagged<TextSize, S>,
+    ) -> (Tagged<TextRange, S>, Tagged<TextRange, S>) {
+        let (before, after) = self.value.split_at(offset.value);
+        (Tagged::new(before), Tagged::new(after))
+    }
+
+    /// The range between two disjoint ranges, if they are disjoint.
+    /// If the ranges touch, the output range is empty.
+    #[inline]
+    pub fn gap(self, other: Tagged<TextRange, S>) -> Option<Tagged<TextRange, S>> {
+        self.value.gap(other.value).map(Tagged::new)
+    }
+
+    /// The offset in this range closest to `offset`.
+    #[inline]
+    pub fn clamp(self, offset: Tagged<TextSize, S>) -> Tagged<TextSize, S> {
+        Tagged::new(self.value.clamp(offset.value))
+    }
+
+    /// Add an offset to this range, within the same space.
+    #[inline]
+    pub fn checked_add(self, offset: TextSize) -> Option<Tagged<TextRange, S>> {
+        self.value.checked_add(offset).map(Tagged::new)
+    }
+
+    /// Subtract an offset from this range, within the same space.
+    #[inline]
+    pub fn checked_sub(self, offset: TextSize) -> Option<Tagged<TextRange, S>> {
+        self.value.checked_sub(offset).map(Tagged::new)
+    }
+
+    /// Relative order of the two ranges (overlapping ranges are considered
+    /// equal).
+    #[inline]
+    pub fn ordering(self, other: Tagged<TextRange, S>) -> Ordering {
+        self.value.ordering(other.value)
+    }
+}
+
+/// Slicing methods, for ranges into the whole text.
+impl Tagged<TextRange, Absolute> {
+    /// The text in this range, if it is in bounds of `text` and on char
+    /// boundaries.
+    #[inline]
+    pub fn get(self, text: &str) -> Option<&str> {
+        self.value.get(text)
+    }
+
+    /// The bytes in this range, if it is in bounds of `bytes`.
+    #[inline]
+    pub fn get_bytes(self, bytes: &[u8]) -> Option<&[u8]> {
+        self.value.get_bytes(bytes)
+    }
+
+    /// Check that this range can be used to slice `text`.
+    #[inline]
+    pub fn checked_for(self, text: &str) -> Result<Tagged<TextRange, Absolute>, RangeError> {
+        self.value.checked_for(text).map(Tagged::new)
+    }
+}
+
+impl<S: Space> Add<TextSize> for Tagged<TextSize, S> {
+    type Output = Tagged<TextSize, S>;
+    #[inline]
+    fn add(self, rhs: TextSize) -> Tagged<TextSize, S> {
+        Tagged::new(self.value + rhs)
+    }
+}
+
+impl<S: Space> Sub<TextSize> for Tagged<TextSize, S> {
+    type Output = Tagged<TextSize, S>;
+    #[inline]
+    fn sub(self, rhs: TextSize) -> Tagged<TextSize, S> {
+        Tagged::new(self.value - rhs)
+    }
+}
+
+impl<S: Space> Sub for Tagged<TextSize, S> {
+    type Output = TextSize;
+    #[inline]
+    fn sub(self, rhs: Tagged<TextSize, S>) -> TextSize {
+        self.value - rhs.value
+    }
+}
+
+impl<S: Space> Add<TextSize> for Tagged<TextRange, S> {
+    type Output = Tagged<TextRange, S>;
+    #[inline]
+    fn add(self, rhs: TextSize) -> Tagged<TextRange, S> {
+        Tagged::new(self.value + rhs)
+    }
+}
+
+impl<S: Space> Sub<TextSize> for Tagged<TextRange, S> {
+    type Output = Tagged<TextRange, S>;
+    #[inline]
+    fn sub(self, rhs: TextSize) -> Tagged<TextRange, S> {
+        Tagged::new(self.value - rhs)
+    }
+}
+
+impl<T, S: Space> AddAssign<TextSize> for Tagged<T, S>
+where
+    Tagged<T, S>: Add<TextSize, Output = Tagged<T, S>> + Copy,
+{
+    #[inline]
+    fn add_assign(&mut self, rhs: TextSize) {
+        *self = *self + rhs
+    }
+}
+
+impl<T, S: Space> SubAssign<TextSize> for Tagged<T, S>
+where
+    Tagged<T, S>: Sub<TextSize, Output = Tagged<T, S>> + Copy,
+{
+    #[inline]
+    fn sub_assign(&mut self, rhs: TextSize) {
+        *self = *self - rhs
+    }
+}
+
+// Only absolute ranges index the whole text.
+macro_rules! index {
+    ($($T:ty => $Output:ty),* $(,)?) => {$(
+        impl Index<Tagged<TextRange, Absolute>> for $T {
+            type Output = $Output;
+            #[inline]
+            fn index(&self, index: Tagged<TextRange, Absolute>) -> &$Output {
+                &self[index.value]
+            }
+        }
+    )*};
+}
+
+macro_rules! index_mut {
+    ($($T:ty),* $(,)?) => {$(
+        impl IndexMut<Tagged<TextRange, Absolute>> for $T {
+            #[inline]
+            fn index_mut(&mut self, index: Tagged<TextRange, Absolute>) -> &mut Self::Output {
+                &mut self[index.value]
+            }
+        }
+    )*};
+}
+
+index! {
+    str => str,
+    String => str,
+    Box<str> => str,
+    Cow<'_, str> => str,
+    Rc<str> => str,
+    Arc<str> => str,
+    [u8] => [u8],
+    Vec<u8> => [u8],
+}
+
+index_mut![str, String, Box<str>, [u8], Vec<u8>];
+
+// Implemented by hand, as derives would require the marker types to implement
+// the traits as well.
+
+impl<T: Clone, S: Space> Clone for Tagged<T, S> {
+    #[inline]
+    fn clone(&self) -> Tagged<T, S> {
+        Tagged::new(self.value.clone())
+    }
+}
+
+impl<T: Copy, S: Space> Copy for Tagged<T, S> {}
+
+impl<T: Default, S: Space> Default for Tagged<T, S> {
+    #[inline]
+    fn default() -> Tagged<T, S> {
+        Tagged::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug, S: Space> fmt::Debug for Tagged<T, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)
+    }
+}
+
+impl<T: PartialEq, S: Space> PartialEq for Tagged<T, S> {
+    #[inline]
+    fn eq(&self, other: &Tagged<T, S>) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq, S: Space> Eq for Tagged<T, S> {}
+
+impl<T: PartialOrd, S: Space> PartialOrd for Tagged<T, S> {
+    #[inline]
+    fn partial_cmp(&self, other: &Tagged<T, S>) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<T: Ord, S: Space> Ord for Tagged<T, S> {
+    #[inline]
+    fn cmp(&self, other: &Tagged<T, S>) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl<T: Hash, S: Space> Hash for Tagged<T, S> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
@@ -0,0 +1,587 @@
+use {
+    crate::{TextRange, TextSize},
+    std::{cmp, collections::VecDeque, error::Error, fmt, slice, vec},
+};
+
+/// A single replacement: the text in `delete` is replaced by `insert`.
+///
+/// Pure insertions have an empty `delete` range, and pure deletions have an
+/// empty `insert` string.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Indel {
+    /// The range of the original text that is removed.
+    pub delete: TextRange,
+    /// The text that is put in place of `delete`.
+    pub insert: String,
+}
+
+impl Indel {
+    /// An indel inserting `text` at `offset`.
+    #[inline]
+    pub fn insert(offset: TextSize, text: String) -> Indel {
+        Indel::replace(TextRange::empty(offset), text)
+    }
+
+    /// An indel deleting the text in `range`.
+    #[inline]
+    pub fn delete(range: TextRange) -> Indel {
+        Indel::replace(range, String::new())
+    }
+
+    /// An indel replacing the text in `range` with `text`.
+    #[inline]
+    pub fn replace(range: TextRange, text: String) -> Indel {
+        Indel {
+            delete: range,
+            insert: text,
+        }
+    }
+
+    /// Applies this indel to `text`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delete` is out of bounds of `text` or is not on char
+    /// boundaries.
+    pub fn apply(&self, text: &mut String) {
+        text.replace_range(std::ops::Range::<usize>::from(self.delete), &self.insert);
+    }
+}
+
+/// A set of [`Indel`]s which do not overlap, sorted by position.
+///
+/// All ranges refer to the original text, before any of the indels is
+/// applied. Use [`TextEditBuilder`] to create one.
+///
+/// # Examples
+///
+/// ```rust
+/// # use text_size::*;
+/// let mut builder = TextEdit::builder();
+/// builder.replace(TextRange::new(4.into(), 9.into()), "slow".to_string());
+/// builder.insert(0.into(), "a ".to_string());
+/// let edit = builder.finish().unwrap();
+///
+/// let mut text = String::from("the quick fox");
+/// edit.apply(&mut text);
+/// assert_eq!(text, "a the slow fox");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TextEdit {
+    // Invariant: sorted by `(start, end)` of `delete`, and no `delete` range
+    // ends after the start of the next one.
+    indels: Vec<Indel>,
+}
+
+/// Collects [`Indel`]s into a [`TextEdit`].
+///
+/// Indels can be added in any order.
+#[derive(Clone, Debug, Default)]
+pub struct TextEditBuilder {
+    indels: Vec<Indel>,
+}
+
+/// The error returned by [`TextEditBuilder::finish`] when two indels overlap.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OverlappingIndels {
+    first: TextRange,
+    second: TextRange,
+}
+
+impl OverlappingIndels {
+    /// The `delete` ranges of the two overlapping indels, in order.
+    #[inline]
+    pub fn ranges(&self) -> (TextRange, TextRange) {
+        (self.first, self.second)
+    }
+}
+
+impl fmt::Display for OverlappingIndels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "overlapping indels: {:?} and {:?}",
+            self.first, self.second
+        )
+    }
+}
+
+impl Error for OverlappingIndels {}
+
+/// Which of two concurrent edits goes first when both insert at the same
+/// offset, see [`TextEdit::transform`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// The edit being transformed inserts before the other one.
+    Left,
+    /// The edit being transformed inserts after the other one.
+    Right,
+}
+
+impl Side {
+    /// The other side.
+    #[inline]
+    pub fn opposite(self) -> Side {
+        match self {
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+impl TextEdit {
+    /// Creates a builder for a new edit.
+    #[inline]
+    pub fn builder() -> TextEditBuilder {
+        TextEditBuilder::default()
+    }
+
+    /// An edit inserting `text` at `offset`.
+    #[inline]
+    pub fn insert(offset: TextSize, text: String) -> TextEdit {
+        TextEdit {
+            indels: vec![Indel::insert(offset, text)],
+        }
+    }
+
+    /// An edit deleting the text in `range`.
+    #[inline]
+    pub fn delete(range: TextRange) -> TextEdit {
+        TextEdit {
+            indels: vec![Indel::delete(range)],
+        }
+    }
+
+    /// An edit replacing the text in `range` with `text`.
+    #[inline]
+    pub fn replace(range: TextRange, text: String) -> TextEdit {
+        TextEdit {
+            indels: vec![Indel::replace(range, text)],
+        }
+    }
+
+    /// The number of indels in this edit.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.indels.len()
+    }
+
+    /// Check if this edit has no indels.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.indels.is_empty()
+    }
+
+    /// Iterates over the indels, in order.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, Indel> {
+        self.indels.iter()
+    }
+
+    /// Applies this edit to `text`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if some indel is out of bounds of `text` or is not on char
+    /// boundaries.
+    pub fn apply(&self, text: &mut String) {
+        match self.indels.as_slice() {
+            [] => return,
+            [indel] => return indel.apply(text),
+            _ => (),
+        }
+
+        let mut len = TextSize::of(&*text);
+        for indel in &self.indels {
+            len += TextSize::of(&indel.insert);
+            len -= indel.delete.len();
+        }
+
+        let mut res = String::with_capacity(len.into());
+        let mut prev = TextSize::from(0);
+        for indel in &self.indels {
+            res.push_str(&text[TextRange::new(prev, indel.delete.start())]);
+            res.push_str(&indel.insert);
+            prev = indel.delete.end();
+        }
+        res.push_str(&text[TextRange::new(prev, TextSize::of(&*text))]);
+        *text = res;
+    }
+
+    /// Maps an offset in the original text to the corresponding offset in
+    /// the edited text.
+    ///
+    /// Returns `None` if the offset is strictly inside a deleted range. An
+    /// offset at the start of a replaced range, or at an insertion point,
+    /// stays before the inserted text; an offset at the end of a replaced
+    /// range moves after it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let edit = TextEdit::replace(TextRange::new(2.into(), 4.into()), "xyz".to_string());
+    /// assert_eq!(edit.apply_to_offset(1.into()), Some(1.into()));
+    /// assert_eq!(edit.apply_to_offset(2.into()), Some(2.into()));
+    /// assert_eq!(edit.apply_to_offset(3.into()), None);
+    /// assert_eq!(edit.apply_to_offset(4.into()), Some(5.into()));
+    /// ```
+    pub fn apply_to_offset(&self, offset: TextSize) -> Option<TextSize> {
+        let mut res = offset;
+        for indel in &self.indels {
+            if indel.delete.start() >= offset {
+                break;
+            }
+            if offset < indel.delete.end() {
+                return None;
+            }
+            res += TextSize::of(&indel.insert);
+            res -= indel.delete.len();
+        }
+        Some(res)
+    }
+
+    /// Maps a range in the original text to the corresponding range in the
+    /// edited text.
+    ///
+    /// Both ends are mapped with [`apply_to_offset`](TextEdit::apply_to_offset),
+    /// so this returns `None` if either is strictly inside a deleted range.
+    pub fn apply_to_range(&self, range: TextRange) -> Option<TextRange> {
+        let start = self.apply_to_offset(range.start())?;
+        let end = self.apply_to_offset(range.end())?;
+        Some(TextRange::new(start, end))
+    }
+
+    /// Combines this edit with `other`, which applies to the text after this
+    /// edit, into a single edit of the original text.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let a = TextEdit::insert(3.into(), "def".to_string());
+    /// let b = TextEdit::delete(TextRange::new(1.into(), 4.into()));
+    ///
+    /// let mut text = String::from("abcghi");
+    /// let mut expected = text.clone();
+    /// a.apply(&mut expected);
+    /// b.apply(&mut expected);
+    ///
+    /// a.compose(&b).apply(&mut text);
+    /// assert_eq!(text, expected);
+    /// assert_eq!(text, "aefghi");
+    /// ```
+    pub fn compose(&self, other: &TextEdit) -> TextEdit {
+        let mut a = Ops::new(self);
+        let mut b = Ops::new(other);
+        let mut res = OpsBuilder::default();
+        while !(a.is_empty() && b.is_empty()) {
+            match (a.peek(), b.peek()) {
+                (Op::Delete(n), _) => {
+                    res.delete(n);
+                    a.skip(n);
+                }
+                (_, Op::Insert(text)) => {
+                    res.insert(text);
+                    b.skip(TextSize::of(text));
+                }
+                (op_a, op_b) => {
+                    let n = cmp::min(op_a.len(), op_b.len());
+                    match (op_a, op_b) {
+                        (Op::Retain(_), Op::Retain(_)) => res.retain(n),
+                        (Op::Retain(_), _) => res.delete(n),
+                        (Op::Insert(text), Op::Retain(_)) => res.insert(&text[TextRange::up_to(n)]),
+                        _ => (),
+                    }
+                    a.skip(n);
+                    b.skip(n);
+                }
+            }
+        }
+        res.finish()
+    }
+
+    /// The edit that undoes this one.
+    ///
+    /// `original_text` is the text this edit applies to.
+    ///
+    /// # Panics
+    ///
+    /// Panics if some indel is out of bounds of `original_text` or is not on
+    /// char boundaries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let edit = TextEdit::replace(TextRange::new(0.into(), 5.into()), "goodbye".to_string());
+    /// let original = "hello world";
+    ///
+    /// let mut text = original.to_string();
+    /// edit.apply(&mut text);
+    /// assert_eq!(text, "goodbye world");
+    ///
+    /// edit.invert(original).apply(&mut text);
+    /// assert_eq!(text, original);
+    /// ```
+    pub fn invert(&self, original_text: &str) -> TextEdit {
+        let mut inserted = TextSize::from(0);
+        let mut deleted = TextSize::from(0);
+        let indels = self
+            .indels
+            .iter()
+            .map(|indel| {
+                let start = indel.delete.start() + inserted - deleted;
+                inserted += TextSize::of(&indel.insert);
+                deleted += indel.delete.len();
+                Indel::replace(
+                    TextRange::at(start, TextSize::of(&indel.insert)),
+                    original_text[indel.delete].to_string(),
+                )
+            })
+            .collect();
+        TextEdit { indels }
+    }
+
+    /// Rebases this edit on top of `other`, where both edits apply to the same
+    /// text.
+    ///
+    /// The result applies to the text after `other`, and has the same effect
+    /// as this edit had on the original text. Text inserted by `other` is
+    /// kept, even if it is inside a range deleted by this edit. `side` decides
+    /// the order of insertions at the same offset.
+    ///
+    /// Transforming two concurrent edits against each other, with opposite
+    /// sides, makes them converge: applying `a` and then
+    /// `b.transform(&a, side.opposite())` gives the same text as applying `b`
+    /// and then `a.transform(&b, side)`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let a = TextEdit::insert(0.into(), "a".to_string());
+    /// let b = TextEdit::insert(0.into(), "b".to_string());
+    ///
+    /// let mut text1 = String::from("text");
+    /// a.apply(&mut text1);
+    /// b.transform(&a, Side::Right).apply(&mut text1);
+    ///
+    /// let mut text2 = String::from("text");
+    /// b.apply(&mut text2);
+    /// a.transform(&b, Side::Left).apply(&mut text2);
+    ///
+    /// assert_eq!(text1, "abtext");
+    /// assert_eq!(text2, "abtext");
+    /// ```
+    pub fn transform(&self, other: &TextEdit, side: Side) -> TextEdit {
+        let mut a = Ops::new(self);
+        let mut b = Ops::new(other);
+        let mut res = OpsBuilder::default();
+        while !a.is_empty() {
+            match (a.peek(), b.peek()) {
+                (Op::Insert(text), Op::Insert(_)) if side == Side::Left => {
+                    res.insert(text);
+                    a.skip(TextSize::of(text));
+                }
+                (Op::Insert(text), Op::Retain(_)) | (Op::Insert(text), Op::Delete(_)) => {
+                    res.insert(text);
+                    a.skip(TextSize::of(text));
+                }
+                (_, Op::Insert(text)) => {
+                    res.retain(TextSize::of(text));
+                    b.skip(TextSize::of(text));
+                }
+                (op_a, op_b) => {
+                    let n = cmp::min(op_a.len(), op_b.len());
+                    match (op_a, op_b) {
+                        (Op::Retain(_), Op::Retain(_)) => res.retain(n),
+                        (Op::Delete(_), Op::Retain(_)) => res.delete(n),
+                        _ => (),
+                    }
+                    a.skip(n);
+                    b.skip(n);
+                }
+            }
+        }
+        res.finish()
+    }
+}
+
+impl IntoIterator for TextEdit {
+    type Item = Indel;
+    type IntoIter = vec::IntoIter<Indel>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.indels.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a TextEdit {
+    type Item = &'a Indel;
+    type IntoIter = slice::Iter<'a, Indel>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl TextEditBuilder {
+    /// Adds an indel inserting `text` at `offset`.
+    #[inline]
+    pub fn insert(&mut self, offset: TextSize, text: String) {
+        self.indel(Indel::insert(offset, text))
+    }
+
+    /// Adds an indel deleting the text in `range`.
+    #[inline]
+    pub fn delete(&mut self, range: TextRange) {
+        self.indel(Indel::delete(range))
+    }
+
+    /// Adds an indel replacing the text in `range` with `text`.
+    #[inline]
+    pub fn replace(&mut self, range: TextRange, text: String) {
+        self.indel(Indel::replace(range, text))
+    }
+
+    /// Adds an indel.
+    #[inline]
+    pub fn indel(&mut self, indel: Indel) {
+        self.indels.push(indel)
+    }
+
+    /// Builds the edit.
+    ///
+    /// Indels are sorted by position. Several insertions at the same offset
+    /// are kept in the order they were added, and an insertion at the start
+    /// of a replaced range comes before the replacement.
+    ///
+    /// Returns an error if the `delete` ranges of two indels overlap, or if
+    /// an insertion is strictly inside another indel's `delete` range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let mut builder = TextEdit::builder();
+    /// builder.delete(TextRange::new(0.into(), 5.into()));
+    /// builder.delete(TextRange::new(3.into(), 8.into()));
+    /// assert!(builder.finish().is_err());
+    /// ```
+    pub fn finish(self) -> Result<TextEdit, OverlappingIndels> {
+        let mut indels = self.indels;
+        indels.sort_by_key(|indel| (indel.delete.start(), indel.delete.end()));
+        for pair in indels.windows(2) {
+            let (first, second) = (pair[0].delete, pair[1].delete);
+            if first.end() > second.start() {
+                return Err(OverlappingIndels { first, second });
+            }
+        }
+        Ok(TextEdit { indels })
+    }
+}
+
+/// A step of an edit, when the edit is seen as a walk over the original text.
+#[derive(Clone, Copy, Debug)]
+enum Op<'a> {
+    /// Keeps this much of the original text.
+    Retain(TextSize),
+    /// Removes this much of the original text.
+    Delete(TextSize),
+    /// Adds new text.
+    Insert(&'a str),
+}
+
+impl Op<'_> {
+    fn len(self) -> TextSize {
+        match self {
+            Op::Retain(n) | Op::Delete(n) => n,
+            Op::Insert(text) => TextSize::of(text),
+        }
+    }
+}
+
+/// The steps of an edit, followed by an endless `Retain`.
+struct Ops<'a> {
+    ops: VecDeque<Op<'a>>,
+}
+
+impl<'a> Ops<'a> {
+    fn new(edit: &'a TextEdit) -> Ops<'a> {
+        let mut ops = VecDeque::new();
+        let mut prev = TextSize::from(0);
+        for indel in &edit.indels {
+            ops.push_back(Op::Retain(indel.delete.start() - prev));
+            ops.push_back(Op::Insert(&indel.insert));
+            ops.push_back(Op::Delete(indel.delete.len()));
+            prev = indel.delete.end();
+        }
+        ops.retain(|op| op.len() > TextSize::from(0));
+        Ops { ops }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.ops.is_empty()
+    }
+
+    fn peek(&self) -> Op<'a> {
+        let rest = Op::Retain(u32::MAX.into());
+        self.ops.front().copied().unwrap_or(rest)
+    }
+
+    fn skip(&mut self, n: TextSize) {
+        let op = match self.ops.front_mut() {
+            Some(it) => it,
+            None => return,
+        };
+        match op {
+            Op::Retain(len) | Op::Delete(len) => *len -= n,
+            Op::Insert(text) => *text = &text[TextRange::new(n, TextSize::of(*text))],
+        }
+        if op.len() == TextSize::from(0) {
+            self.ops.pop_front();
+        }
+    }
+}
+
+/// Collects steps back into indels.
+#[derive(Default)]
+struct OpsBuilder {
+    indels: Vec<Indel>,
+    offset: TextSize,
+}
+
+impl OpsBuilder {
+    fn retain(&mut self, n: TextSize) {
+        self.offset += n;
+    }
+
+    fn delete(&mut self, n: TextSize) {
+        let indel = self.current();
+        indel.delete = TextRange::at(indel.delete.start(), indel.delete.len() + n);
+        self.offset += n;
+    }
+
+    fn insert(&mut self, text: &str) {
+        self.current().insert.push_str(text);
+    }
+
+    /// The indel ending at the current offset, which steps are added to.
+    fn current(&mut self) -> &mut Indel {
+        let offset = self.offset;
+        match self.indels.last() {
+            Some(indel) if indel.delete.end() == offset => (),
+            _ => self.indels.push(Indel::insert(offset, String::new())),
+        }
+        self.indels.last_mut().unwrap()
+    }
+
+    fn finish(self) -> TextEdit {
+        TextEdit {
+            indels: self.indels,
+        }
+    }
+}
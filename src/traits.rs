@@ -1,36 +1,204 @@
-use {crate::TextSize, std::convert::TryInto};
+use {
+    crate::{TextSize, TextSize64, Utf16Size},
+    std::{borrow::Cow, convert::TryInto, ffi::OsStr, rc::Rc, sync::Arc},
+};
 
-use priv_in_pub::Sealed;
-mod priv_in_pub {
-    pub trait Sealed {}
-}
-
-/// Primitives with a textual length that can be passed to [`TextSize::of`].
-pub trait TextLen: Copy + Sealed {
-    /// The textual length of this primitive.
+/// Text-like objects with a length that can be passed to [`TextSize::of`] and
+/// [`TextSize64::of`].
+///
+/// This is implemented for std string types, and can be implemented for other
+/// text containers, such as ropes. The length is the number of UTF-8 bytes.
+///
+/// # Panics
+///
+/// Implementations for std types panic if the length does not fit in a
+/// [`TextSize`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use text_size::*;
+/// #[derive(Clone, Copy)]
+/// struct Rope<'a>(&'a [&'a str]);
+///
+/// impl TextLen for Rope<'_> {
+///     fn text_len(self) -> TextSize {
+///         self.0.iter().copied().map(TextSize::of).sum()
+///     }
+/// }
+///
+/// assert_eq!(TextSize::of(Rope(&["hello", " ", "world"])), TextSize::from(11));
+/// ```
+pub trait TextLen: Copy {
+    /// The textual length of this object.
     fn text_len(self) -> TextSize;
+
+    /// The textual length of this object, as a [`TextSize64`].
+    ///
+    /// Unlike [`text_len`](TextLen::text_len), the implementations for std
+    /// types do not panic on texts of 4 GiB or more.
+    #[inline]
+    fn text_len64(self) -> TextSize64 {
+        self.text_len().into()
+    }
 }
 
-impl Sealed for &'_ str {}
 impl TextLen for &'_ str {
     #[inline]
     fn text_len(self) -> TextSize {
         self.len().try_into().unwrap()
     }
+
+    #[inline]
+    fn text_len64(self) -> TextSize64 {
+        TextSize64::new(self.len() as u64)
+    }
 }
 
-impl Sealed for &'_ String {}
 impl TextLen for &'_ String {
     #[inline]
     fn text_len(self) -> TextSize {
         self.as_str().text_len()
     }
+
+    #[inline]
+    fn text_len64(self) -> TextSize64 {
+        self.as_str().text_len64()
+    }
+}
+
+impl TextLen for &'_ Box<str> {
+    #[inline]
+    fn text_len(self) -> TextSize {
+        (**self).text_len()
+    }
+
+    #[inline]
+    fn text_len64(self) -> TextSize64 {
+        (**self).text_len64()
+    }
+}
+
+impl TextLen for &'_ Cow<'_, str> {
+    #[inline]
+    fn text_len(self) -> TextSize {
+        (**self).text_len()
+    }
+
+    #[inline]
+    fn text_len64(self) -> TextSize64 {
+        (**self).text_len64()
+    }
+}
+
+impl TextLen for &'_ Rc<str> {
+    #[inline]
+    fn text_len(self) -> TextSize {
+        (**self).text_len()
+    }
+
+    #[inline]
+    fn text_len64(self) -> TextSize64 {
+        (**self).text_len64()
+    }
+}
+
+impl TextLen for &'_ Arc<str> {
+    #[inline]
+    fn text_len(self) -> TextSize {
+        (**self).text_len()
+    }
+
+    #[inline]
+    fn text_len64(self) -> TextSize64 {
+        (**self).text_len64()
+    }
+}
+
+/// The length of the bytes, which need not be valid UTF-8.
+impl TextLen for &'_ [u8] {
+    #[inline]
+    fn text_len(self) -> TextSize {
+        self.len().try_into().unwrap()
+    }
+
+    #[inline]
+    fn text_len64(self) -> TextSize64 {
+        TextSize64::new(self.len() as u64)
+    }
+}
+
+/// The length of the platform-specific encoding, see
+/// [`OsStr::as_encoded_bytes`].
+impl TextLen for &'_ OsStr {
+    #[inline]
+    fn text_len(self) -> TextSize {
+        self.as_encoded_bytes().text_len()
+    }
+
+    #[inline]
+    fn text_len64(self) -> TextSize64 {
+        self.as_encoded_bytes().text_len64()
+    }
 }
 
-impl Sealed for char {}
 impl TextLen for char {
     #[inline]
     fn text_len(self) -> TextSize {
         (self.len_utf8() as u32).into()
     }
 }
+
+/// Text-like objects with a UTF-16 length that can be passed to
+/// [`Utf16Size::of`].
+///
+/// This is implemented for std string types, and can be implemented for other
+/// text containers, such as ropes. The length is the number of UTF-16 code
+/// units.
+///
+/// # Panics
+///
+/// Implementations for std types panic if the length does not fit in a
+/// [`Utf16Size`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use text_size::*;
+/// #[derive(Clone, Copy)]
+/// struct Rope<'a>(&'a [&'a str]);
+///
+/// impl Utf16Len for Rope<'_> {
+///     fn utf16_len(self) -> Utf16Size {
+///         self.0.iter().copied().map(Utf16Size::of).sum()
+///     }
+/// }
+///
+/// assert_eq!(Utf16Size::of(Rope(&["hello", " ", "🦀"])), Utf16Size::from(8));
+/// ```
+pub trait Utf16Len: Copy {
+    /// The length of this primitive in UTF-16 code units.
+    fn utf16_len(self) -> Utf16Size;
+}
+
+impl Utf16Len for &'_ str {
+    #[inline]
+    fn utf16_len(self) -> Utf16Size {
+        let len: usize = self.chars().map(char::len_utf16).sum();
+        len.try_into().unwrap()
+    }
+}
+
+impl Utf16Len for &'_ String {
+    #[inline]
+    fn utf16_len(self) -> Utf16Size {
+        self.as_str().utf16_len()
+    }
+}
+
+impl Utf16Len for char {
+    #[inline]
+    fn utf16_len(self) -> Utf16Size {
+        (self.len_utf16() as u32).into()
+    }
+}
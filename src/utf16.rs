@@ -0,0 +1,144 @@
+use {
+    crate::{TextRange, TextSize, Utf16Len},
+    std::convert::TryFrom,
+};
+
+/// A measure of text length in UTF-16 code units. Also, equivalently, an
+/// index into text.
+///
+/// This is the unit used by JavaScript strings and by many editor protocols.
+/// It is kept distinct from [`TextSize`], which counts UTF-8 bytes, so that
+/// the two can not be mixed up. Converting between them requires the text,
+/// see [`Utf16Size::from_text_size`] and [`Utf16Size::to_text_size`].
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Utf16Size {
+    raw: u32,
+}
+
+size_impls!(Utf16Size(u32), "UTF-16 code units");
+
+impl From<Utf16Size> for usize {
+    #[inline]
+    fn from(value: Utf16Size) -> Self {
+        value.raw as usize
+    }
+}
+
+impl Utf16Size {
+    /// The UTF-16 size of some primitive text-like object.
+    ///
+    /// Accepts `char`, `&str`, and `&String`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let char_size = Utf16Size::of('🦀');
+    /// assert_eq!(char_size, Utf16Size::from(2));
+    ///
+    /// let str_size = Utf16Size::of("größe");
+    /// assert_eq!(str_size, Utf16Size::from(5));
+    /// ```
+    #[inline]
+    pub fn of<T: Utf16Len>(text: T) -> Utf16Size {
+        text.utf16_len()
+    }
+
+    /// The UTF-16 offset in `text` of the UTF-8 `offset`.
+    ///
+    /// Returns `None` if `offset` is out of bounds of `text` or is not on a
+    /// char boundary.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let text = "🦀 crab";
+    /// assert_eq!(Utf16Size::from_text_size(text, 5.into()), Some(3.into()));
+    /// assert_eq!(Utf16Size::from_text_size(text, 2.into()), None);
+    /// ```
+    pub fn from_text_size(text: &str, offset: TextSize) -> Option<Utf16Size> {
+        let prefix = text.get(..usize::from(offset))?;
+        Some(Utf16Size::of(prefix))
+    }
+
+    /// The UTF-8 offset in `text` of this UTF-16 offset.
+    ///
+    /// Returns `None` if this offset is out of bounds of `text` or is in the
+    /// middle of a surrogate pair.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let text = "🦀 crab";
+    /// assert_eq!(Utf16Size::from(3).to_text_size(text), Some(5.into()));
+    /// assert_eq!(Utf16Size::from(1).to_text_size(text), None);
+    /// ```
+    pub fn to_text_size(self, text: &str) -> Option<TextSize> {
+        let mut utf16 = 0;
+        for (i, c) in text.char_indices() {
+            if utf16 >= self.raw {
+                return if utf16 == self.raw {
+                    TextSize::try_from(i).ok()
+                } else {
+                    None
+                };
+            }
+            utf16 += c.len_utf16() as u32;
+        }
+        if utf16 == self.raw {
+            TextSize::try_from(text.len()).ok()
+        } else {
+            None
+        }
+    }
+}
+
+/// A range in text, represented as a pair of [`Utf16Size`].
+///
+/// It is a logic error for `start` to be greater than `end`.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Utf16Range {
+    // Invariant: start <= end
+    start: Utf16Size,
+    end: Utf16Size,
+}
+
+range_impls!(Utf16Range(Utf16Size));
+
+/// Conversion methods.
+impl Utf16Range {
+    /// The UTF-16 range in `text` of the UTF-8 `range`.
+    ///
+    /// Returns `None` if `range` is out of bounds of `text` or is not on char
+    /// boundaries.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use text_size::*;
+    /// let text = "🦀 crab";
+    /// let range = TextRange::new(5.into(), 9.into());
+    /// let utf16 = Utf16Range::from_text_range(text, range).unwrap();
+    /// assert_eq!(utf16, Utf16Range::new(3.into(), 7.into()));
+    /// assert_eq!(utf16.to_text_range(text), Some(range));
+    /// ```
+    pub fn from_text_range(text: &str, range: TextRange) -> Option<Utf16Range> {
+        let start = Utf16Size::from_text_size(text, range.start())?;
+        let rest = text.get(usize::from(range.start())..)?;
+        let len = Utf16Size::from_text_size(rest, range.len())?;
+        Some(Utf16Range::at(start, len))
+    }
+
+    /// The UTF-8 range in `text` of this UTF-16 range.
+    ///
+    /// Returns `None` if this range is out of bounds of `text` or either end is
+    /// in the middle of a surrogate pair.
+    pub fn to_text_range(self, text: &str) -> Option<TextRange> {
+        let start = self.start().to_text_size(text)?;
+        let rest = text.get(usize::from(start)..)?;
+        let len = self.len().to_text_size(rest)?;
+        Some(TextRange::at(start, len))
+    }
+}
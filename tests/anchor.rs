@@ -0,0 +1,148 @@
+mod common;
+
+use {common::Rng, std::ops, text_size::*};
+
+fn size(x: u32) -> TextSize {
+    TextSize::from(x)
+}
+
+fn range(x: ops::Range<u32>) -> TextRange {
+    TextRange::new(x.start.into(), x.end.into())
+}
+
+fn moved(offset: u32, bias: Bias, replacements: &[(TextRange, u32)]) -> TextSize {
+    let mut anchors = AnchorSet::new();
+    let id = anchors.insert(Anchor::new(size(offset), bias));
+    anchors.edit(replacements.iter().map(|&(range, len)| (range, size(len))));
+    anchors.get(id).unwrap().offset
+}
+
+#[test]
+fn outside_of_edits() {
+    let edits = [(range(2..4), 5), (range(8..8), 3)];
+    for &bias in &[Bias::Left, Bias::Right] {
+        assert_eq!(moved(0, bias, &edits), size(0));
+        assert_eq!(moved(1, bias, &edits), size(1));
+        assert_eq!(moved(5, bias, &edits), size(8));
+        assert_eq!(moved(7, bias, &edits), size(10));
+        assert_eq!(moved(9, bias, &edits), size(15));
+    }
+}
+
+#[test]
+fn at_insertion_point() {
+    let edits = [(range(3..3), 4)];
+    assert_eq!(moved(3, Bias::Left, &edits), size(3));
+    assert_eq!(moved(3, Bias::Right, &edits), size(7));
+}
+
+#[test]
+fn inside_of_replacement() {
+    let edits = [(range(2..6), 1)];
+    for &offset in &[2, 4, 6] {
+        assert_eq!(moved(offset, Bias::Left, &edits), size(2));
+        assert_eq!(moved(offset, Bias::Right, &edits), size(3));
+    }
+    assert_eq!(moved(7, Bias::Left, &edits), size(4));
+}
+
+#[test]
+fn between_touching_edits() {
+    let edits = [(range(2..4), 1), (range(4..4), 2), (range(4..6), 3)];
+    assert_eq!(moved(4, Bias::Left, &edits), size(2));
+    assert_eq!(moved(4, Bias::Right, &edits), size(8));
+}
+
+#[test]
+fn from_text_edit() {
+    let mut builder = TextEdit::builder();
+    builder.replace(range(0..3), "let".to_string());
+    builder.insert(size(7), " = 92".to_string());
+    let edit = builder.finish().unwrap();
+
+    let mut anchors: AnchorSet = vec![
+        Anchor::new(size(7), Bias::Left),
+        Anchor::new(size(7), Bias::Right),
+    ]
+    .into_iter()
+    .collect();
+    anchors.edit(
+        edit.iter()
+            .map(|it| (it.delete, TextSize::of(it.insert.as_str()))),
+    );
+
+    let offsets: Vec<_> = anchors.iter().map(|(_, it)| it.offset).collect();
+    assert_eq!(offsets, vec![size(7), size(12)]);
+}
+
+#[test]
+fn remove() {
+    let mut anchors = AnchorSet::new();
+    let a = anchors.insert(Anchor::new(size(1), Bias::Left));
+    let b = anchors.insert(Anchor::new(size(2), Bias::Right));
+    assert_eq!(anchors.len(), 2);
+
+    assert_eq!(anchors.remove(a), Some(Anchor::new(size(1), Bias::Left)));
+    assert_eq!(anchors.remove(a), None);
+    assert_eq!(anchors.get(a), None);
+    assert_eq!(anchors.len(), 1);
+
+    anchors.edit(vec![(range(0..0), size(3))]);
+    assert_eq!(anchors.get(b), Some(Anchor::new(size(5), Bias::Right)));
+}
+
+#[test]
+#[should_panic]
+fn unsorted_edits() {
+    moved(0, Bias::Left, &[(range(4..5), 1), (range(1..2), 1)]);
+}
+
+/// Checks every replacement in turn, instead of binary searching.
+fn moved_naive(offset: u32, bias: Bias, replacements: &[(TextRange, u32)]) -> TextSize {
+    let mut shifted = offset as i64;
+    let mut delta = 0;
+    let mut touching = Vec::new();
+    for &(range, len) in replacements {
+        let (start, end) = (u32::from(range.start()), u32::from(range.end()));
+        let new_start = start as i64 + delta;
+        if start <= offset && offset <= end {
+            touching.push((new_start, new_start + len as i64));
+        }
+        delta += len as i64 - (end - start) as i64;
+        if end < offset {
+            shifted = offset as i64 + delta;
+        }
+    }
+    let res = match bias {
+        Bias::Left => touching.first().map_or(shifted, |it| it.0),
+        Bias::Right => touching.last().map_or(shifted, |it| it.1),
+    };
+    size(res as u32)
+}
+
+#[test]
+fn matches_naive() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for _ in 0..500 {
+        let mut replacements = Vec::new();
+        let mut start = 0;
+        for _ in 0..rng.below(5) {
+            start += rng.below(3);
+            let end = start + rng.below(3);
+            replacements.push((range(start..end), rng.below(4)));
+            start = end;
+        }
+        for offset in 0..start + 3 {
+            for &bias in &[Bias::Left, Bias::Right] {
+                assert_eq!(
+                    moved(offset, bias, &replacements),
+                    moved_naive(offset, bias, &replacements),
+                    "{} {:?} {:?}",
+                    offset,
+                    bias,
+                    replacements,
+                );
+            }
+        }
+    }
+}
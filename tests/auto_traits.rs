@@ -1,10 +1,11 @@
 use {
     static_assertions::*,
     std::{
-        fmt::Debug,
+        fmt::{Debug, Display},
         hash::Hash,
         marker::{Send, Sync},
         panic::{RefUnwindSafe, UnwindSafe},
+        str::FromStr,
     },
     text_size::*,
 };
@@ -12,7 +13,27 @@ use {
 // auto traits
 assert_impl_all!(TextSize: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
 assert_impl_all!(TextRange: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(Utf16Size: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(Utf16Range: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(TextSize64: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(TextRange64: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(OptTextSize: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
+assert_impl_all!(OptTextRange: Send, Sync, Unpin, UnwindSafe, RefUnwindSafe);
 
 // common traits
 assert_impl_all!(TextSize: Copy, Debug, Default, Hash, Ord);
 assert_impl_all!(TextRange: Copy, Debug, Default, Hash, Eq);
+assert_impl_all!(Utf16Size: Copy, Debug, Default, Hash, Ord);
+assert_impl_all!(Utf16Range: Copy, Debug, Default, Hash, Eq);
+assert_impl_all!(TextSize64: Copy, Debug, Default, Hash, Ord);
+assert_impl_all!(TextRange64: Copy, Debug, Default, Hash, Eq);
+assert_impl_all!(OptTextSize: Copy, Debug, Default, Hash, Eq);
+assert_impl_all!(OptTextRange: Copy, Debug, Default, Hash, Eq);
+
+// text representation
+assert_impl_all!(TextSize: Display, FromStr);
+assert_impl_all!(TextRange: Display, FromStr);
+
+// niche-optimized options
+assert_eq_size!(OptTextSize, TextSize);
+assert_eq_size!(OptTextRange, TextRange);
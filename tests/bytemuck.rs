@@ -0,0 +1,54 @@
+use {
+    bytemuck::checked::{self, CheckedCastError},
+    std::ops,
+    text_size::*,
+};
+
+fn size(x: u32) -> TextSize {
+    TextSize::from(x)
+}
+
+fn range(x: ops::Range<u32>) -> TextRange {
+    TextRange::new(x.start.into(), x.end.into())
+}
+
+#[test]
+fn size_is_pod() {
+    let raw: &[u32] = &[0, 92, u32::MAX];
+    let sizes: &[TextSize] = bytemuck::cast_slice(raw);
+    assert_eq!(sizes, &[size(0), size(92), TextSize::MAX]);
+    assert_eq!(bytemuck::cast_slice::<TextSize, u32>(sizes), raw);
+    assert_eq!(<TextSize as bytemuck::Zeroable>::zeroed(), TextSize::ZERO);
+}
+
+#[test]
+fn range_round_trip() {
+    let ranges = [range(0..0), range(1..5), range(5..92)];
+    let bytes: &[u8] = bytemuck::cast_slice(&ranges);
+    assert_eq!(bytes.len(), 24);
+    assert_eq!(
+        checked::try_cast_slice::<u8, TextRange>(bytes),
+        Ok(&ranges[..])
+    );
+    assert_eq!(
+        checked::try_from_bytes::<TextRange>(bytemuck::bytes_of(&ranges[1])),
+        Ok(&ranges[1])
+    );
+    assert_eq!(
+        <TextRange as bytemuck::Zeroable>::zeroed(),
+        TextRange::default()
+    );
+}
+
+#[test]
+fn invalid_range_is_rejected() {
+    let raw: &[u32] = &[1, 5, 7, 7, 92, 62];
+    assert_eq!(
+        checked::try_cast_slice::<u32, TextRange>(raw),
+        Err(CheckedCastError::InvalidBitPattern)
+    );
+    assert_eq!(
+        checked::try_cast_slice::<u32, TextRange>(&raw[..4]),
+        Ok(&[range(1..5), range(7..7)][..])
+    );
+}
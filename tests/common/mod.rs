@@ -0,0 +1,36 @@
+//! Helpers shared by the integration tests.
+#![allow(dead_code)]
+
+/// A tiny xorshift generator, so that the randomized tests are reproducible.
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn below(&mut self, n: u32) -> u32 {
+        (self.next() % n as u64) as u32
+    }
+
+    /// Up to `max_chars` chars picked from `alphabet`.
+    pub fn text(&mut self, alphabet: &[char], max_chars: u32) -> String {
+        let n = self.below(max_chars + 1);
+        (0..n)
+            .map(|_| alphabet[self.below(alphabet.len() as u32) as usize])
+            .collect()
+    }
+
+    /// A char boundary of `text`, the end included.
+    pub fn char_boundary(&mut self, text: &str) -> usize {
+        let n = text.chars().count() as u32;
+        text.char_indices()
+            .map(|(i, _)| i)
+            .chain(Some(text.len()))
+            .nth(self.below(n + 1) as usize)
+            .unwrap()
+    }
+}
@@ -1,9 +1,12 @@
-use text_size::TextSize;
+use {
+    std::{borrow::Cow, ffi::OsStr, rc::Rc, sync::Arc},
+    text_size::{TextLen, TextSize},
+};
 
 #[derive(Copy, Clone)]
 struct BadRope<'a>(&'a [&'a str]);
 
-impl BadRope<'_> {
+impl TextLen for BadRope<'_> {
     fn text_len(self) -> TextSize {
         self.0.iter().copied().map(TextSize::of).sum()
     }
@@ -20,5 +23,18 @@ fn main() {
     let x: &String = &"hello".into();
     let _ = TextSize::of(x);
 
-    let _ = BadRope(&[""]).text_len();
+    let _ = TextSize::of(BadRope(&[""]));
+}
+
+#[test]
+fn std_text_types() {
+    let five = TextSize::from(5);
+    assert_eq!(TextSize::of(&Box::<str>::from("hello")), five);
+    assert_eq!(TextSize::of(&Cow::Borrowed("hello")), five);
+    assert_eq!(TextSize::of(&Cow::<str>::Owned("hello".to_string())), five);
+    assert_eq!(TextSize::of(&Rc::<str>::from("hello")), five);
+    assert_eq!(TextSize::of(&Arc::<str>::from("hello")), five);
+    assert_eq!(TextSize::of(&b"hello"[..]), five);
+    assert_eq!(TextSize::of(OsStr::new("hello")), five);
+    assert_eq!(TextSize::of(BadRope(&["he", "llo"])), five);
 }
@@ -1,8 +1,105 @@
-use text_size::*;
+use {
+    std::{borrow::Cow, rc::Rc, sync::Arc},
+    text_size::*,
+};
 
 #[test]
 fn main() {
     let range = TextRange::default();
-    &""[range];
-    &String::new()[range];
+    let _ = &""[range];
+    let _ = &String::new()[range];
+    let _ = &Box::<str>::from("")[range];
+    let _ = &Cow::Borrowed("")[range];
+    let _ = &Rc::<str>::from("")[range];
+    let _ = &Arc::<str>::from("")[range];
+    let _ = &b""[..][range];
+    let _ = &Vec::<u8>::new()[range];
+}
+
+fn range(start: u32, end: u32) -> TextRange {
+    TextRange::new(start.into(), end.into())
+}
+
+#[test]
+fn get() {
+    let text = "a🦀b";
+    assert_eq!(range(0, 1).get(text), Some("a"));
+    assert_eq!(range(1, 5).get(text), Some("🦀"));
+    assert_eq!(range(6, 6).get(text), Some(""));
+    assert_eq!(range(1, 3).get(text), None);
+    assert_eq!(range(2, 5).get(text), None);
+    assert_eq!(range(5, 7).get(text), None);
+}
+
+#[test]
+fn checked_for() {
+    let text = "a🦀b";
+    assert_eq!(range(1, 5).checked_for(text), Ok(range(1, 5)));
+    assert_eq!(
+        range(2, 7).checked_for(text),
+        Err(RangeError::OutOfBounds {
+            range: range(2, 7),
+            len: 6.into()
+        })
+    );
+    assert_eq!(
+        range(2, 5).checked_for(text),
+        Err(RangeError::NotCharBoundary { offset: 2.into() })
+    );
+    assert_eq!(
+        range(1, 4).checked_for(text),
+        Err(RangeError::NotCharBoundary { offset: 4.into() })
+    );
+    assert_eq!(
+        range(2, 7).checked_for(text).unwrap_err().to_string(),
+        "range 2..7 is out of bounds of text of length 6"
+    );
+    assert_eq!(
+        range(1, 4).checked_for(text).unwrap_err().to_string(),
+        "offset 4 is not on a char boundary"
+    );
+}
+
+#[test]
+fn char_boundaries() {
+    let text = "a🦀b";
+    let floors = [0, 1, 1, 1, 1, 5, 6, 6];
+    let ceils = [0, 1, 5, 5, 5, 5, 6, 6];
+    for offset in 0..8 {
+        let size = TextSize::from(offset);
+        assert_eq!(
+            size.floor_char_boundary(text),
+            floors[offset as usize].into()
+        );
+        assert_eq!(size.ceil_char_boundary(text), ceils[offset as usize].into());
+    }
+}
+
+#[test]
+fn bytes() {
+    let mut bytes = b"let x = 92;".to_vec();
+    assert_eq!(&bytes[range(4, 5)], b"x");
+    assert_eq!(&bytes[..][range(8, 10)], b"92");
+    bytes[range(4, 5)].copy_from_slice(b"y");
+    assert_eq!(bytes, b"let y = 92;");
+
+    assert_eq!(range(4, 5).get_bytes(&bytes), Some(&b"y"[..]));
+    assert_eq!(range(4, 12).get_bytes(&bytes), None);
+}
+
+fn slice<T: std::ops::Index<TextRange, Output = str> + ?Sized>(text: &T, range: TextRange) -> &str {
+    &text[range]
+}
+
+#[test]
+fn shared_strings() {
+    let text = "fn main() {}";
+    assert_eq!(slice(&Box::<str>::from(text), range(3, 7)), "main");
+    assert_eq!(slice(&Cow::Borrowed(text), range(3, 7)), "main");
+    assert_eq!(slice(&Rc::<str>::from(text), range(3, 7)), "main");
+    assert_eq!(slice(&Arc::<str>::from(text), range(3, 7)), "main");
+
+    let mut boxed = Box::<str>::from(text);
+    boxed[range(3, 7)].make_ascii_uppercase();
+    assert_eq!(&*boxed, "fn MAIN() {}");
 }
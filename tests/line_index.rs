@@ -0,0 +1,298 @@
+mod common;
+
+use {common::Rng, std::ops, text_size::*};
+
+fn size(x: u32) -> TextSize {
+    TextSize::from(x)
+}
+
+fn range(x: ops::Range<u32>) -> TextRange {
+    TextRange::new(x.start.into(), x.end.into())
+}
+
+fn line_col(line: u32, col: u32) -> LineCol {
+    LineCol { line, col }
+}
+
+#[test]
+fn empty_text() {
+    let index = LineIndex::new("");
+    assert_eq!(index.line_count(), 1);
+    assert_eq!(index.line_col(size(0)), line_col(0, 0));
+    assert_eq!(index.offset(line_col(0, 0)), Some(size(0)));
+    assert_eq!(index.offset(line_col(0, 1)), None);
+    assert_eq!(index.offset(line_col(1, 0)), None);
+    assert_eq!(index.line_range(0), Some(range(0..0)));
+}
+
+#[test]
+fn line_col_roundtrip() {
+    let text = "hello\nworld\n\nfoo";
+    let index = LineIndex::new(text);
+    assert_eq!(index.line_count(), 4);
+
+    let mut line = 0;
+    let mut col = 0;
+    for (i, c) in text.char_indices() {
+        let offset = size(i as u32);
+        assert_eq!(index.line_col(offset), line_col(line, col));
+        assert_eq!(index.offset(line_col(line, col)), Some(offset));
+        if c == '\n' {
+            line += 1;
+            col = 0;
+        } else {
+            col += c.len_utf8() as u32;
+        }
+    }
+    assert_eq!(index.line_col(TextSize::of(text)), line_col(3, 3));
+}
+
+#[test]
+fn line_ranges() {
+    let index = LineIndex::new("hello\nworld\n\nfoo\n");
+    assert_eq!(index.line_count(), 5);
+    assert_eq!(index.line_range(0), Some(range(0..5)));
+    assert_eq!(index.line_range(1), Some(range(6..11)));
+    assert_eq!(index.line_range(2), Some(range(12..12)));
+    assert_eq!(index.line_range(3), Some(range(13..16)));
+    assert_eq!(index.line_range(4), Some(range(17..17)));
+    assert_eq!(index.line_range(5), None);
+}
+
+#[test]
+fn offset_past_line_end() {
+    let index = LineIndex::new("ab\ncd");
+    assert_eq!(index.offset(line_col(0, 2)), Some(size(2)));
+    assert_eq!(index.offset(line_col(0, 3)), None);
+    assert_eq!(index.offset(line_col(1, 2)), Some(size(5)));
+    assert_eq!(index.offset(line_col(1, 3)), None);
+    assert_eq!(index.offset(line_col(0, u32::MAX)), None);
+}
+
+#[test]
+#[should_panic]
+fn line_col_out_of_bounds() {
+    LineIndex::new("ab").line_col(size(3));
+}
+
+fn wide_line_col(line: u32, col: u32) -> WideLineCol {
+    WideLineCol { line, col }
+}
+
+#[test]
+fn wide_columns_roundtrip() {
+    let text = "aé🦀b\nx\u{2014}y\n\n🦀🦀";
+    let index = LineIndex::new(text);
+
+    let mut line = 0;
+    let (mut utf8, mut utf16, mut utf32) = (0, 0, 0);
+    for (i, c) in text.char_indices().chain(Some((text.len(), '\n'))) {
+        let lc = index.line_col(size(i as u32));
+        assert_eq!(lc, line_col(line, utf8));
+        let expected = [
+            (WideEncoding::Utf16, wide_line_col(line, utf16)),
+            (WideEncoding::Utf32, wide_line_col(line, utf32)),
+        ];
+        for &(enc, wide) in expected.iter() {
+            assert_eq!(index.to_wide(enc, lc), Some(wide));
+            assert_eq!(index.to_utf8(enc, wide), Some(lc));
+        }
+        if c == '\n' {
+            line += 1;
+            utf8 = 0;
+            utf16 = 0;
+            utf32 = 0;
+        } else {
+            utf8 += c.len_utf8() as u32;
+            utf16 += c.len_utf16() as u32;
+            utf32 += 1;
+        }
+    }
+}
+
+#[test]
+fn wide_columns_inside_char() {
+    let index = LineIndex::new("a🦀b");
+    for col in 2..5 {
+        assert_eq!(index.to_wide(WideEncoding::Utf16, line_col(0, col)), None);
+        assert_eq!(index.to_wide(WideEncoding::Utf32, line_col(0, col)), None);
+    }
+    assert_eq!(
+        index.to_utf8(WideEncoding::Utf16, wide_line_col(0, 2)),
+        None,
+    );
+    assert_eq!(
+        index.to_utf8(WideEncoding::Utf32, wide_line_col(0, 2)),
+        Some(line_col(0, 5)),
+    );
+}
+
+#[test]
+fn wide_columns_out_of_bounds() {
+    let index = LineIndex::new("é\n🦀");
+    assert_eq!(index.to_wide(WideEncoding::Utf16, line_col(0, 3)), None);
+    assert_eq!(index.to_wide(WideEncoding::Utf16, line_col(2, 0)), None);
+    assert_eq!(
+        index.to_utf8(WideEncoding::Utf16, wide_line_col(0, 2)),
+        None,
+    );
+    assert_eq!(
+        index.to_utf8(WideEncoding::Utf16, wide_line_col(1, 3)),
+        None,
+    );
+    assert_eq!(
+        index.to_utf8(WideEncoding::Utf32, wide_line_col(1, u32::MAX)),
+        None,
+    );
+    assert_eq!(
+        index.to_utf8(WideEncoding::Utf32, wide_line_col(2, 0)),
+        None,
+    );
+}
+
+const ALPHABET: &[char] = &[
+    'a', 'b', '\n', '\n', '\r', '\r', 'é', '\u{2014}', '🦀', '\u{85}', '\u{2028}',
+];
+
+const ALL_TERMINATORS: [LineTerminators; 3] = [
+    LineTerminators::Lf,
+    LineTerminators::LfCrLf,
+    LineTerminators::Unicode,
+];
+
+fn check_edit(text: &str, delete: TextRange, insert: &str) {
+    let mut edited = text.to_string();
+    edited.replace_range(ops::Range::<usize>::from(delete), insert);
+
+    for &terminators in ALL_TERMINATORS.iter() {
+        let mut index = LineIndex::with_terminators(text, terminators);
+        index.edit(delete, insert);
+        assert_eq!(
+            index,
+            LineIndex::with_terminators(&edited, terminators),
+            "text: {:?}, delete: {:?}, insert: {:?}, terminators: {:?}",
+            text,
+            delete,
+            insert,
+            terminators,
+        );
+    }
+}
+
+#[test]
+fn edit_examples() {
+    check_edit("", range(0..0), "");
+    check_edit("", range(0..0), "a\nb");
+    check_edit("abc", range(1..2), "\n");
+    check_edit("a\nb\nc", range(0..5), "");
+    check_edit("a\nb\nc", range(1..4), "x");
+    check_edit("é\n🦀é\nc", range(3..7), "\n—\n");
+    check_edit("é🦀é\n", range(2..6), "é");
+    check_edit("ab\n🦀", range(3..3), "\n\n");
+    check_edit("ab\n🦀", range(7..7), "é\n");
+}
+
+#[test]
+fn edit_joins_and_splits_crlf() {
+    check_edit("a\rb\nc", range(2..3), "");
+    check_edit("a\r\nc", range(2..2), "b");
+    check_edit("a\r\nc", range(1..1), "\r");
+    check_edit("a\r\nc", range(2..2), "\n");
+    check_edit("a\rc", range(2..2), "\n\r");
+    check_edit("a\r\r\nc", range(2..3), "");
+    check_edit("\r\n", range(1..2), "\u{85}");
+    check_edit("\r\r", range(1..1), "\n");
+}
+
+#[test]
+fn edit_matches_rebuild() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for _ in 0..2000 {
+        let text = rng.text(ALPHABET, 16);
+        let a = rng.char_boundary(&text);
+        let b = rng.char_boundary(&text);
+        let delete = range(a.min(b) as u32..a.max(b) as u32);
+        let insert = rng.text(ALPHABET, 6);
+        check_edit(&text, delete, &insert);
+    }
+}
+
+#[test]
+fn edit_sequence_matches_rebuild() {
+    for &terminators in ALL_TERMINATORS.iter() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        let mut text = rng.text(ALPHABET, 64);
+        let mut index = LineIndex::with_terminators(&text, terminators);
+        for _ in 0..500 {
+            let a = rng.char_boundary(&text);
+            let b = rng.char_boundary(&text);
+            let delete = range(a.min(b) as u32..a.max(b) as u32);
+            let insert = rng.text(ALPHABET, 6);
+
+            index.edit(delete, &insert);
+            text.replace_range(ops::Range::<usize>::from(delete), &insert);
+            assert_eq!(index, LineIndex::with_terminators(&text, terminators));
+        }
+    }
+}
+
+#[test]
+fn line_terminators() {
+    let text = "a\nb\r\nc\rd\u{85}e\u{2028}f\u{2029}g";
+    let ranges = |terminators| {
+        let index = LineIndex::with_terminators(text, terminators);
+        (0..index.line_count())
+            .map(|line| {
+                let content = index.line_range(line).unwrap();
+                let full = index.line_range_with_terminator(line).unwrap();
+                (&text[content], &text[full])
+            })
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        ranges(LineTerminators::Lf),
+        vec![
+            ("a", "a\n"),
+            ("b\r", "b\r\n"),
+            (
+                "c\rd\u{85}e\u{2028}f\u{2029}g",
+                "c\rd\u{85}e\u{2028}f\u{2029}g"
+            ),
+        ],
+    );
+    assert_eq!(
+        ranges(LineTerminators::LfCrLf),
+        vec![
+            ("a", "a\n"),
+            ("b", "b\r\n"),
+            (
+                "c\rd\u{85}e\u{2028}f\u{2029}g",
+                "c\rd\u{85}e\u{2028}f\u{2029}g"
+            ),
+        ],
+    );
+    assert_eq!(
+        ranges(LineTerminators::Unicode),
+        vec![
+            ("a", "a\n"),
+            ("b", "b\r\n"),
+            ("c", "c\r"),
+            ("d", "d\u{85}"),
+            ("e", "e\u{2028}"),
+            ("f", "f\u{2029}"),
+            ("g", "g"),
+        ],
+    );
+}
+
+#[test]
+fn line_col_with_crlf() {
+    let index = LineIndex::with_terminators("ab\r\ncd", LineTerminators::LfCrLf);
+    assert_eq!(index.line_col(size(2)), line_col(0, 2));
+    assert_eq!(index.line_col(size(3)), line_col(0, 3));
+    assert_eq!(index.line_col(size(4)), line_col(1, 0));
+    assert_eq!(index.offset(line_col(0, 2)), Some(size(2)));
+    assert_eq!(index.offset(line_col(0, 3)), None);
+    assert_eq!(index.offset(line_col(1, 2)), Some(size(6)));
+}
@@ -19,6 +19,17 @@ fn sum() {
 fn math() {
     assert_eq!(size(10) + size(5), size(15));
     assert_eq!(size(10) - size(5), size(5));
+    assert_eq!(size(10) * 3, size(30));
+    assert_eq!(3 * size(10), size(30));
+    assert_eq!(size(10) / 3, size(3));
+    assert_eq!(size(10) % 3, size(1));
+    assert_eq!(size(10) % size(4), size(2));
+
+    let mut x = size(10);
+    x *= 4;
+    x /= 8;
+    x %= size(3);
+    assert_eq!(x, size(2));
 }
 
 #[test]
@@ -27,6 +38,51 @@ fn checked_math() {
     assert_eq!(size(1).checked_sub(size(1)), Some(size(0)));
     assert_eq!(size(1).checked_sub(size(2)), None);
     assert_eq!(size(!0).checked_add(size(1)), None);
+    assert_eq!(size(2).checked_mul(3), Some(size(6)));
+    assert_eq!(size(!0).checked_mul(2), None);
+    assert_eq!(size(7).checked_div(2), Some(size(3)));
+    assert_eq!(size(7).checked_div(0), None);
+    assert_eq!(size(7).checked_rem(4), Some(size(3)));
+    assert_eq!(size(7).checked_rem(0), None);
+    assert_eq!(size(5).checked_next_multiple_of(4), Some(size(8)));
+    assert_eq!(size(5).checked_next_multiple_of(0), None);
+    assert_eq!(size(!0).checked_next_multiple_of(2), None);
+}
+
+#[test]
+fn saturating_wrapping_overflowing_math() {
+    assert_eq!(TextSize::MAX.saturating_add(size(1)), TextSize::MAX);
+    assert_eq!(size(1).saturating_sub(size(2)), TextSize::ZERO);
+    assert_eq!(size(3).saturating_sub(size(2)), size(1));
+    assert_eq!(size(!0).saturating_mul(2), TextSize::MAX);
+    assert_eq!(size(7).saturating_div(2), size(3));
+
+    assert_eq!(TextSize::MAX.wrapping_add(size(2)), size(1));
+    assert_eq!(size(1).wrapping_sub(size(2)), TextSize::MAX);
+    assert_eq!(size(!0).wrapping_mul(2), size(!0 - 1));
+    assert_eq!(size(7).wrapping_div(2), size(3));
+    assert_eq!(size(7).wrapping_rem(2), size(1));
+
+    assert_eq!(
+        TextSize::MAX.overflowing_add(size(1)),
+        (TextSize::ZERO, true)
+    );
+    assert_eq!(size(1).overflowing_add(size(1)), (size(2), false));
+    assert_eq!(size(0).overflowing_sub(size(1)), (TextSize::MAX, true));
+    assert_eq!(size(!0).overflowing_mul(2), (size(!0 - 1), true));
+    assert_eq!(size(7).overflowing_div(2), (size(3), false));
+    assert_eq!(size(7).overflowing_rem(2), (size(1), false));
+
+    assert_eq!(size(7).div_ceil(2), size(4));
+    assert_eq!(size(5).next_multiple_of(4), size(8));
+    assert!(size(8).is_multiple_of(4));
+    assert!(!size(5).is_multiple_of(4));
+    assert!(size(0).is_multiple_of(0));
+    assert_eq!(size(3).midpoint(size(8)), size(5));
+    assert_eq!(TextSize::MAX.midpoint(TextSize::MAX), TextSize::MAX);
+
+    assert_eq!(size(3).abs_diff(size(8)), size(5));
+    assert_eq!(size(8).abs_diff(size(3)), size(5));
 }
 
 #[test]
@@ -74,3 +130,78 @@ fn contains_point() {
     assert!(   range(1..3).contains_inclusive(size(3)));
     assert!( ! range(1..3).contains_inclusive(size(4)));
 }
+
+#[test]
+fn subtract() {
+    assert_eq!(
+        range(1..5).subtract(range(2..3)),
+        (Some(range(1..2)), Some(range(3..5)))
+    );
+    assert_eq!(range(1..5).subtract(range(0..3)), (None, Some(range(3..5))));
+    assert_eq!(range(1..5).subtract(range(3..9)), (Some(range(1..3)), None));
+    assert_eq!(range(1..5).subtract(range(0..9)), (None, None));
+    assert_eq!(range(1..5).subtract(range(7..9)), (Some(range(1..5)), None));
+    assert_eq!(range(1..5).subtract(range(0..1)), (None, Some(range(1..5))));
+    assert_eq!(
+        range(1..5).subtract(range(3..3)),
+        (Some(range(1..3)), Some(range(3..5)))
+    );
+    assert_eq!(range(3..3).subtract(range(5..6)), (None, None));
+}
+
+#[test]
+fn split_at() {
+    assert_eq!(range(1..5).split_at(size(1)), (range(1..1), range(1..5)));
+    assert_eq!(range(1..5).split_at(size(3)), (range(1..3), range(3..5)));
+    assert_eq!(range(1..5).split_at(size(5)), (range(1..5), range(5..5)));
+}
+
+#[test]
+#[should_panic]
+fn split_at_out_of_range() {
+    range(1..5).split_at(size(6));
+}
+
+#[test]
+fn gap() {
+    assert_eq!(range(1..2).gap(range(4..5)), Some(range(2..4)));
+    assert_eq!(range(4..5).gap(range(1..2)), Some(range(2..4)));
+    assert_eq!(range(1..2).gap(range(2..3)), Some(range(2..2)));
+    assert_eq!(range(2..2).gap(range(2..3)), Some(range(2..2)));
+    assert_eq!(range(1..3).gap(range(2..3)), None);
+    assert_eq!(range(1..3).gap(range(2..2)), None);
+}
+
+#[test]
+fn clamp() {
+    assert_eq!(range(1..3).clamp(size(0)), size(1));
+    assert_eq!(range(1..3).clamp(size(2)), size(2));
+    assert_eq!(range(1..3).clamp(size(4)), size(3));
+    assert_eq!(range(2..2).clamp(size(4)), size(2));
+}
+
+#[test]
+fn try_new() {
+    assert_eq!(TextRange::try_new(size(1), size(3)), Ok(range(1..3)));
+    assert_eq!(TextRange::try_new(size(3), size(3)), Ok(range(3..3)));
+
+    let err = TextRange::try_new(size(3), size(1)).unwrap_err();
+    assert_eq!((err.start(), err.end()), (size(3), size(1)));
+    assert_eq!(err.to_string(), "invalid range: 3..1");
+}
+
+const KEYWORDS: [TextRange; 3] = [
+    TextRange::new(TextSize::new(0), TextSize::new(2)),
+    TextRange::at(TextSize::new(3), TextSize::new(5)),
+    TextRange::empty(TextSize::new(9)),
+];
+
+const SHIFTED: Option<TextRange> = KEYWORDS[1].checked_add(TextSize::new(1));
+
+#[test]
+fn const_constructors() {
+    assert_eq!(KEYWORDS, [range(0..2), range(3..8), range(9..9)]);
+    assert_eq!(SHIFTED, Some(range(4..9)));
+    assert_eq!(TextRange::up_to(TextSize::new(3)), range(0..3));
+    assert_eq!(KEYWORDS[0].checked_sub(TextSize::new(1)), None);
+}
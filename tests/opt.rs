@@ -0,0 +1,64 @@
+use {
+    std::{convert::TryFrom, ops},
+    text_size::*,
+};
+
+fn size(x: u32) -> TextSize {
+    TextSize::from(x)
+}
+
+fn range(x: ops::Range<u32>) -> TextRange {
+    TextRange::new(x.start.into(), x.end.into())
+}
+
+#[test]
+fn opt_text_size() {
+    for &value in &[
+        None,
+        Some(size(0)),
+        Some(size(92)),
+        Some(size(u32::MAX - 1)),
+    ] {
+        let opt = OptTextSize::try_from(value).unwrap();
+        assert_eq!(opt.get(), value);
+        assert_eq!(Option::<TextSize>::from(opt), value);
+        assert_eq!(opt.is_some(), value.is_some());
+        assert_eq!(format!("{:?}", opt), format!("{:?}", value));
+    }
+    assert_eq!(OptTextSize::default(), OptTextSize::NONE);
+}
+
+#[test]
+#[should_panic]
+fn opt_text_size_max() {
+    OptTextSize::some(TextSize::MAX);
+}
+
+#[test]
+fn opt_text_size_max_checked() {
+    assert_eq!(OptTextSize::checked_some(TextSize::MAX), None);
+    let err = OptTextSize::try_from(Some(TextSize::MAX)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "TextSize::MAX can not be stored in OptTextSize"
+    );
+}
+
+#[test]
+fn opt_text_range() {
+    let values = [
+        None,
+        Some(range(0..0)),
+        Some(range(1..5)),
+        Some(TextRange::new(TextSize::MAX, TextSize::MAX)),
+        Some(TextRange::up_to(TextSize::MAX)),
+    ];
+    for &value in &values {
+        let opt = OptTextRange::from(value);
+        assert_eq!(opt.get(), value);
+        assert_eq!(Option::<TextRange>::from(opt), value);
+        assert_eq!(opt.is_none(), value.is_none());
+        assert_eq!(format!("{:?}", opt), format!("{:?}", value));
+    }
+    assert_eq!(OptTextRange::default(), OptTextRange::NONE);
+}
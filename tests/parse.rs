@@ -0,0 +1,96 @@
+mod common;
+
+use {
+    common::Rng,
+    std::{error::Error, ops},
+    text_size::*,
+};
+
+fn size(x: u32) -> TextSize {
+    TextSize::from(x)
+}
+
+fn range(x: ops::Range<u32>) -> TextRange {
+    TextRange::new(x.start.into(), x.end.into())
+}
+
+#[test]
+fn display() {
+    assert_eq!(size(92).to_string(), "92");
+    assert_eq!(format!("{:>4}", size(7)), "   7");
+    assert_eq!(range(1..5).to_string(), "1..5");
+    assert_eq!(TextRange::empty(size(3)).to_string(), "3..3");
+}
+
+#[test]
+fn parse_size() {
+    assert_eq!("0".parse(), Ok(size(0)));
+    assert_eq!("4294967295".parse(), Ok(TextSize::MAX));
+    assert!("".parse::<TextSize>().is_err());
+    assert!("-1".parse::<TextSize>().is_err());
+    assert!("4294967296".parse::<TextSize>().is_err());
+    assert!(" 1".parse::<TextSize>().is_err());
+}
+
+#[test]
+fn parse_range() {
+    assert_eq!("1..5".parse(), Ok(range(1..5)));
+    assert_eq!("1..=5".parse(), Ok(range(1..6)));
+    assert_eq!("1+5".parse(), Ok(range(1..6)));
+    assert_eq!("3".parse(), Ok(range(3..3)));
+    assert_eq!("3..3".parse(), Ok(range(3..3)));
+    assert_eq!("3+0".parse(), Ok(range(3..3)));
+}
+
+#[test]
+fn parse_range_errors() {
+    let parse = |s: &str| s.parse::<TextRange>().unwrap_err();
+
+    for s in [
+        "", "..", "1..", "..5", "1+", "a..b", "1...5", "1..5..6", "1 .. 5",
+    ] {
+        assert!(matches!(parse(s), ParseRangeError::Int(_)), "{:?}", s);
+    }
+
+    for s in ["+1..2", "1..+2", "-1..2", "5++5", "5+-5", "5..=+6"] {
+        assert_eq!(parse(s), ParseRangeError::Sign, "{:?}", s);
+    }
+    assert_eq!(
+        parse("+1..2").to_string(),
+        "invalid text range: unexpected sign"
+    );
+    assert!(parse("+1..2").source().is_none());
+
+    let err = parse("5..1");
+    assert_eq!(
+        err,
+        ParseRangeError::Invalid(TextRange::try_new(size(5), size(1)).unwrap_err())
+    );
+    assert_eq!(err.to_string(), "invalid range: 5..1");
+    assert!(err.source().is_some());
+    assert!(matches!(parse("5..=3"), ParseRangeError::Invalid(_)));
+    assert!(matches!(parse("3..=2"), ParseRangeError::Invalid(_)));
+
+    assert_eq!(parse("0..=4294967295"), ParseRangeError::Overflow);
+    assert_eq!(parse("1+4294967295"), ParseRangeError::Overflow);
+    assert!(parse("1+4294967295").source().is_none());
+    assert_eq!("0+4294967295".parse(), Ok(TextRange::up_to(TextSize::MAX)));
+}
+
+#[test]
+fn round_trip() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for _ in 0..1000 {
+        let (a, b) = (rng.next() as u32, rng.next() as u32);
+        let (start, end) = (size(a.min(b)), size(a.max(b)));
+        assert_eq!(start.to_string().parse(), Ok(start));
+
+        let range = TextRange::new(start, end);
+        assert_eq!(range.to_string().parse(), Ok(range));
+        assert_eq!(format!("{}+{}", start, range.len()).parse(), Ok(range));
+        if !range.is_empty() {
+            let last = end - size(1);
+            assert_eq!(format!("{}..={}", start, last).parse(), Ok(range));
+        }
+    }
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use {common::Rng, std::ops, text_size::*};
+
+fn size(x: u32) -> TextSize {
+    TextSize::from(x)
+}
+
+fn range(x: ops::Range<u32>) -> TextRange {
+    TextRange::new(x.start.into(), x.end.into())
+}
+
+fn values<'a>(iter: impl Iterator<Item = (TextRange, &'a u32)>) -> Vec<u32> {
+    iter.map(|(_, &it)| it).collect()
+}
+
+#[test]
+fn queries() {
+    let map: TextRangeMap<u32> = vec![
+        (range(0..10), 0),
+        (range(2..4), 1),
+        (range(4..4), 2),
+        (range(6..12), 3),
+        (range(12..14), 4),
+    ]
+    .into_iter()
+    .collect();
+
+    assert_eq!(values(map.overlapping(range(4..6))), vec![0, 1, 2, 3]);
+    assert_eq!(values(map.overlapping(range(11..11))), vec![3]);
+    assert!(values(map.overlapping(range(15..20))).is_empty());
+    assert_eq!(values(map.containing(size(4))), vec![0]);
+    assert_eq!(values(map.containing(size(12))), vec![4]);
+    assert!(values(map.containing(size(14))).is_empty());
+    assert!(values(map.containing(size(u32::MAX))).is_empty());
+}
+
+#[test]
+fn insert_and_remove() {
+    let mut map = TextRangeMap::new();
+    map.insert(range(0..5), 0);
+    map.insert(range(2..3), 1);
+    map.insert(range(0..5), 2);
+    assert_eq!(map.len(), 3);
+    assert_eq!(values(map.iter()), vec![0, 2, 1]);
+
+    assert_eq!(map.remove(range(0..5)), Some(0));
+    assert_eq!(map.remove(range(0..4)), None);
+    assert_eq!(values(map.containing(size(2))), vec![2, 1]);
+
+    map.retain(|range, _| range.len() > size(1));
+    assert_eq!(values(map.iter()), vec![2]);
+    assert_eq!(values(map.containing(size(2))), vec![2]);
+}
+
+/// A random range, mostly short, sometimes long.
+fn random_range(rng: &mut Rng) -> TextRange {
+    let start = rng.below(100);
+    let len = if rng.below(4) == 0 {
+        rng.below(50)
+    } else {
+        rng.below(5)
+    };
+    range(start..start + len)
+}
+
+#[test]
+fn matches_brute_force() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for round in 0..200 {
+        let mut entries: Vec<(TextRange, u32)> = (0..rng.below(40))
+            .map(|i| (random_range(&mut rng), i))
+            .collect();
+        let mut map: TextRangeMap<u32> = entries.iter().cloned().collect();
+        if round % 2 == 0 {
+            let (range, value) = (random_range(&mut rng), 1000);
+            map.insert(range, value);
+            entries.push((range, value));
+            if let Some(&(range, _)) = entries.first() {
+                let removed = map.remove(range).unwrap();
+                let i = entries
+                    .iter()
+                    .position(|&it| it == (range, removed))
+                    .unwrap();
+                entries.remove(i);
+            }
+        }
+        entries.sort_by_key(|&(it, _)| (it.start(), it.end()));
+
+        for _ in 0..20 {
+            let query = random_range(&mut rng);
+            let expected: Vec<u32> = entries
+                .iter()
+                .filter(|(it, _)| it.intersect(query).is_some())
+                .map(|&(_, it)| it)
+                .collect();
+            assert_eq!(values(map.overlapping(query)), expected);
+
+            let offset = query.start();
+            let expected: Vec<u32> = entries
+                .iter()
+                .filter(|(it, _)| it.contains(offset))
+                .map(|&(_, it)| it)
+                .collect();
+            assert_eq!(values(map.containing(offset)), expected);
+        }
+    }
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use {common::Rng, std::ops, text_size::*};
+
+fn size(x: u32) -> TextSize {
+    TextSize::from(x)
+}
+
+fn range(x: ops::Range<u32>) -> TextRange {
+    TextRange::new(x.start.into(), x.end.into())
+}
+
+fn set(ranges: &[ops::Range<u32>]) -> TextRangeSet {
+    ranges.iter().cloned().map(range).collect()
+}
+
+fn ranges(set: &TextRangeSet) -> Vec<ops::Range<u32>> {
+    set.iter()
+        .map(|it| it.start().into()..it.end().into())
+        .collect()
+}
+
+#[test]
+fn coalesces() {
+    assert_eq!(
+        ranges(&set(&[3..5, 0..1, 1..2, 4..8, 9..9])),
+        vec![0..2, 3..8]
+    );
+    assert_eq!(set(&[0..2, 2..4]), set_of(range(0..4)));
+    assert!(set(&[1..1, 5..5]).is_empty());
+}
+
+#[test]
+fn insert_and_remove() {
+    let mut set = set(&[0..2, 4..6, 8..10]);
+    set.insert(range(2..4));
+    assert_eq!(ranges(&set), vec![0..6, 8..10]);
+    set.insert(range(12..14));
+    assert_eq!(ranges(&set), vec![0..6, 8..10, 12..14]);
+    set.remove(range(1..9));
+    assert_eq!(ranges(&set), vec![0..1, 9..10, 12..14]);
+    set.remove(range(10..12));
+    assert_eq!(ranges(&set), vec![0..1, 9..10, 12..14]);
+    set.remove(range(0..20));
+    assert!(set.is_empty());
+}
+
+#[test]
+#[rustfmt::skip]
+fn contains() {
+    let set = set(&[1..3, 5..6]);
+    assert!( ! set.contains(size(0)));
+    assert!(   set.contains(size(1)));
+    assert!(   set.contains(size(2)));
+    assert!( ! set.contains(size(3)));
+    assert!(   set.contains(size(5)));
+    assert!( ! set.contains(size(6)));
+}
+
+#[test]
+fn set_algebra() {
+    let a = set(&[0..4, 6..10, 12..13]);
+    let b = set(&[2..7, 9..12]);
+    assert_eq!(a.union(&b), set_of(range(0..13)));
+    assert_eq!(ranges(&a.intersection(&b)), vec![2..4, 6..7, 9..10]);
+    assert_eq!(ranges(&a.difference(&b)), vec![0..2, 7..9, 12..13]);
+    assert_eq!(ranges(&b.difference(&a)), vec![4..6, 10..12]);
+    assert_eq!(
+        ranges(&a.complement_within(range(3..14))),
+        vec![4..6, 10..12, 13..14]
+    );
+}
+
+fn random_range(rng: &mut Rng) -> TextRange {
+    let start = rng.below(UNIVERSE);
+    let len = rng.below(6);
+    range(start..(start + len).min(UNIVERSE))
+}
+
+fn random_set(rng: &mut Rng) -> TextRangeSet {
+    let n = rng.below(6);
+    (0..n).map(|_| random_range(rng)).collect()
+}
+
+const UNIVERSE: u32 = 32;
+
+/// The set as a bitmask over `0..UNIVERSE`.
+fn bits(set: &TextRangeSet) -> u64 {
+    let res = (0..UNIVERSE)
+        .filter(|&i| set.contains(size(i)))
+        .fold(0, |acc, i| acc | 1 << i);
+    let expected = set
+        .iter()
+        .flat_map(|it| u32::from(it.start())..u32::from(it.end()))
+        .fold(0, |acc, i| acc | 1 << i);
+    assert_eq!(res, expected);
+    res
+}
+
+#[test]
+fn matches_bitmask() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for _ in 0..2000 {
+        let a = random_set(&mut rng);
+        let b = random_set(&mut rng);
+        let (x, y) = (bits(&a), bits(&b));
+        assert_eq!(bits(&a.union(&b)), x | y);
+        assert_eq!(bits(&a.intersection(&b)), x & y);
+        assert_eq!(bits(&a.difference(&b)), x & !y);
+
+        let within = random_range(&mut rng);
+        assert_eq!(
+            bits(&a.complement_within(within)),
+            !x & bits(&set_of(within))
+        );
+
+        let mut c = a.clone();
+        c.insert(within);
+        assert_eq!(c, a.union(&set_of(within)));
+        let mut c = a.clone();
+        c.remove(within);
+        assert_eq!(c, a.difference(&set_of(within)));
+    }
+}
+
+fn set_of(range: TextRange) -> TextRangeSet {
+    Some(range).into_iter().collect()
+}
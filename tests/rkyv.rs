@@ -0,0 +1,77 @@
+use {
+    rkyv::{
+        api::low::{access, deserialize, to_bytes_in_with_alloc, LowSerializer},
+        rancor::Failure,
+        ser::{allocator::SubAllocator, writer::Buffer},
+        util::Align,
+        Serialize,
+    },
+    std::{mem::MaybeUninit, ops},
+    text_size::*,
+};
+
+// The tests use rkyv's allocation-free API, so that they only need the
+// features which the `rkyv` feature of this crate enables.
+type Serializer<'a, 'b> = LowSerializer<Buffer<'a>, SubAllocator<'b>, Failure>;
+
+fn to_bytes<'a>(
+    value: &impl for<'b> Serialize<Serializer<'a, 'b>>,
+    output: &'a mut Align<[MaybeUninit<u8>; 256]>,
+) -> Buffer<'a> {
+    let mut alloc = [MaybeUninit::<u8>::uninit(); 256];
+    to_bytes_in_with_alloc(
+        value,
+        Buffer::from(&mut **output),
+        SubAllocator::new(&mut alloc),
+    )
+    .unwrap()
+}
+
+fn size(x: u32) -> TextSize {
+    TextSize::from(x)
+}
+
+fn range(x: ops::Range<u32>) -> TextRange {
+    TextRange::new(x.start.into(), x.end.into())
+}
+
+#[test]
+fn size_round_trip() {
+    let mut output = Align([MaybeUninit::uninit(); 256]);
+    let bytes = to_bytes(&size(92), &mut output);
+    let archived = access::<ArchivedTextSize, Failure>(&bytes).unwrap();
+    assert_eq!(archived.to_native(), size(92));
+    assert_eq!(
+        deserialize::<TextSize, Failure>(archived).unwrap(),
+        size(92)
+    );
+}
+
+#[test]
+fn ranges_round_trip() {
+    let ranges = [range(0..0), range(1..5), range(5..92)];
+    let mut output = Align([MaybeUninit::uninit(); 256]);
+    let bytes = to_bytes(&ranges, &mut output);
+    let archived = access::<[ArchivedTextRange; 3], Failure>(&bytes).unwrap();
+    let in_place: Vec<TextRange> = archived.iter().map(|it| it.to_native()).collect();
+    assert_eq!(in_place, ranges);
+    assert_eq!(archived[1].start(), size(1));
+    assert_eq!(archived[1].end(), size(5));
+    assert_eq!(
+        deserialize::<[TextRange; 3], Failure>(archived).unwrap(),
+        ranges
+    );
+}
+
+#[test]
+fn invalid_range_is_rejected() {
+    // An archived `(u32, u32)` has the same layout as an archived range.
+    let mut output = Align([MaybeUninit::uninit(); 256]);
+    let bytes = to_bytes(&(5u32, 1u32), &mut output);
+    assert!(access::<ArchivedTextRange, Failure>(&bytes).is_err());
+
+    let mut output = Align([MaybeUninit::uninit(); 256]);
+    let bytes = to_bytes(&(1u32, 5u32), &mut output);
+    let archived = access::<ArchivedTextRange, Failure>(&bytes).unwrap();
+    assert_eq!(archived.to_native(), range(1..5));
+}
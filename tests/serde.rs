@@ -1,4 +1,9 @@
-use {serde_test::*, std::ops, text_size::*};
+use {
+    serde::{Deserialize, Serialize},
+    serde_test::*,
+    std::ops,
+    text_size::*,
+};
 
 fn size(x: u32) -> TextSize {
     TextSize::from(x)
@@ -77,3 +82,197 @@ fn invalid_range_deserialization() {
         "invalid range: 92..62",
     );
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SizeAsString(#[serde(with = "text_size::serde_helpers::text_size::as_string")] TextSize);
+
+#[test]
+fn size_as_string() {
+    assert_tokens(
+        &SizeAsString(size(0)),
+        &[
+            Token::NewtypeStruct {
+                name: "SizeAsString",
+            },
+            Token::Str("0"),
+        ],
+    );
+    assert_tokens(
+        &SizeAsString(TextSize::MAX),
+        &[
+            Token::NewtypeStruct {
+                name: "SizeAsString",
+            },
+            Token::Str("4294967295"),
+        ],
+    );
+    assert_de_tokens(
+        &SizeAsString(size(92)),
+        &[
+            Token::NewtypeStruct {
+                name: "SizeAsString",
+            },
+            Token::String("92"),
+        ],
+    );
+    assert_de_tokens_error::<SizeAsString>(
+        &[
+            Token::NewtypeStruct {
+                name: "SizeAsString",
+            },
+            Token::Str("-1"),
+        ],
+        "invalid digit found in string",
+    );
+    assert_de_tokens_error::<SizeAsString>(
+        &[
+            Token::NewtypeStruct {
+                name: "SizeAsString",
+            },
+            Token::U32(92),
+        ],
+        "invalid type: integer `92`, expected a text size string",
+    );
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct RangeAsStruct(#[serde(with = "text_size::serde_helpers::text_range::as_struct")] TextRange);
+
+#[test]
+fn range_as_struct() {
+    let name = "RangeAsStruct";
+    assert_tokens(
+        &RangeAsStruct(range(1..5)),
+        &[
+            Token::NewtypeStruct { name },
+            Token::Struct {
+                name: "TextRange",
+                len: 2,
+            },
+            Token::Str("start"),
+            Token::U32(1),
+            Token::Str("end"),
+            Token::U32(5),
+            Token::StructEnd,
+        ],
+    );
+    assert_de_tokens(
+        &RangeAsStruct(range(1..5)),
+        &[
+            Token::NewtypeStruct { name },
+            Token::Map { len: None },
+            Token::String("end"),
+            Token::U32(5),
+            Token::Str("file"),
+            Token::Str("main.rs"),
+            Token::Str("start"),
+            Token::U32(1),
+            Token::MapEnd,
+        ],
+    );
+    assert_de_tokens(
+        &RangeAsStruct(range(1..5)),
+        &[
+            Token::NewtypeStruct { name },
+            Token::Seq { len: Some(2) },
+            Token::U32(1),
+            Token::U32(5),
+            Token::SeqEnd,
+        ],
+    );
+    assert_de_tokens_error::<RangeAsStruct>(
+        &[
+            Token::NewtypeStruct { name },
+            Token::Struct {
+                name: "TextRange",
+                len: 2,
+            },
+            Token::Str("start"),
+            Token::U32(5),
+            Token::Str("end"),
+            Token::U32(1),
+            Token::StructEnd,
+        ],
+        "invalid range: 5..1",
+    );
+    assert_de_tokens_error::<RangeAsStruct>(
+        &[
+            Token::NewtypeStruct { name },
+            Token::Struct {
+                name: "TextRange",
+                len: 1,
+            },
+            Token::Str("start"),
+            Token::U32(1),
+            Token::StructEnd,
+        ],
+        "missing field `end`",
+    );
+    assert_de_tokens_error::<RangeAsStruct>(
+        &[
+            Token::NewtypeStruct { name },
+            Token::Struct {
+                name: "TextRange",
+                len: 2,
+            },
+            Token::Str("start"),
+            Token::U32(1),
+            Token::Str("start"),
+        ],
+        "duplicate field `start`",
+    );
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct RangeAsStartLen(
+    #[serde(with = "text_size::serde_helpers::text_range::as_start_len")] TextRange,
+);
+
+#[test]
+fn range_as_start_len() {
+    let name = "RangeAsStartLen";
+    assert_tokens(
+        &RangeAsStartLen(range(10..15)),
+        &[
+            Token::NewtypeStruct { name },
+            Token::Tuple { len: 2 },
+            Token::U32(10),
+            Token::U32(5),
+            Token::TupleEnd,
+        ],
+    );
+    assert_de_tokens_error::<RangeAsStartLen>(
+        &[
+            Token::NewtypeStruct { name },
+            Token::Tuple { len: 2 },
+            Token::U32(1),
+            Token::U32(u32::MAX),
+            Token::TupleEnd,
+        ],
+        "text range end overflowed",
+    );
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct RangeAsString(#[serde(with = "text_size::serde_helpers::text_range::as_string")] TextRange);
+
+#[test]
+fn range_as_string() {
+    let name = "RangeAsString";
+    assert_tokens(
+        &RangeAsString(range(1..5)),
+        &[Token::NewtypeStruct { name }, Token::Str("1..5")],
+    );
+    assert_de_tokens(
+        &RangeAsString(range(1..5)),
+        &[Token::NewtypeStruct { name }, Token::String("1+4")],
+    );
+    assert_de_tokens(
+        &RangeAsString(range(1..5)),
+        &[Token::NewtypeStruct { name }, Token::BorrowedStr("1..=4")],
+    );
+    assert_de_tokens_error::<RangeAsString>(
+        &[Token::NewtypeStruct { name }, Token::Str("5..1")],
+        "invalid range: 5..1",
+    );
+}
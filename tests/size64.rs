@@ -0,0 +1,146 @@
+use {
+    std::{convert::TryFrom, ops},
+    text_size::*,
+};
+
+const GIB: u64 = 1 << 30;
+
+fn size(x: u64) -> TextSize64 {
+    TextSize64::from(x)
+}
+
+fn range(x: ops::Range<u64>) -> TextRange64 {
+    TextRange64::new(x.start.into(), x.end.into())
+}
+
+#[test]
+fn math_past_u32() {
+    assert_eq!(size(3 * GIB) + size(2 * GIB), size(5 * GIB));
+    assert_eq!(size(5 * GIB) - size(2 * GIB), size(3 * GIB));
+    assert_eq!(size(GIB) * 8, size(8 * GIB));
+    assert_eq!(size(9 * GIB) / 3, size(3 * GIB));
+    assert_eq!(size(5 * GIB + 1) % size(GIB), size(1));
+    assert_eq!(TextSize64::MAX.checked_add(size(1)), None);
+    assert_eq!(size(1).saturating_sub(size(2)), TextSize64::ZERO);
+    assert_eq!(
+        vec![size(3 * GIB), size(3 * GIB)]
+            .into_iter()
+            .sum::<TextSize64>(),
+        size(6 * GIB)
+    );
+
+    let r = range(4 * GIB..6 * GIB);
+    assert_eq!(r.len(), size(2 * GIB));
+    assert_eq!(r + size(GIB), range(5 * GIB..7 * GIB));
+    assert_eq!(
+        r.intersect(range(5 * GIB..8 * GIB)),
+        Some(range(5 * GIB..6 * GIB))
+    );
+    assert_eq!(r.cover(range(0..1)), range(0..6 * GIB));
+    assert!(r.contains(size(5 * GIB)));
+    assert_eq!(
+        r.split_at(size(5 * GIB)),
+        (range(4 * GIB..5 * GIB), range(5 * GIB..6 * GIB))
+    );
+    assert_eq!(
+        r.gap(range(7 * GIB..8 * GIB)),
+        Some(range(6 * GIB..7 * GIB))
+    );
+    assert_eq!(r.clamp(size(0)), size(4 * GIB));
+}
+
+#[test]
+fn widening_and_narrowing() {
+    let small = TextSize::from(u32::MAX);
+    assert_eq!(TextSize64::from(small), size(u32::MAX as u64));
+    assert_eq!(TextSize::try_from(size(u32::MAX as u64)), Ok(small));
+    assert!(TextSize::try_from(size(u32::MAX as u64 + 1)).is_err());
+
+    let small = TextRange::new(1.into(), 5.into());
+    assert_eq!(TextRange64::from(small), range(1..5));
+    assert_eq!(TextRange::try_from(range(1..5)), Ok(small));
+    assert!(TextRange::try_from(range(1..5 * GIB)).is_err());
+}
+
+#[test]
+fn constructors() {
+    const R: TextRange64 = TextRange64::at(TextSize64::new(5 * GIB), TextSize64::new(GIB));
+    assert_eq!(R, range(5 * GIB..6 * GIB));
+    assert_eq!(
+        TextRange64::try_new(size(1), size(5 * GIB)),
+        Ok(range(1..5 * GIB))
+    );
+
+    let err = TextRange64::try_new(size(5 * GIB), size(1)).unwrap_err();
+    assert_eq!((err.start(), err.end()), (size(5 * GIB), size(1)));
+    assert_eq!(err.to_string(), "invalid range: 5368709120..1");
+}
+
+#[test]
+fn parse_and_display() {
+    assert_eq!("5368709120".parse(), Ok(size(5 * GIB)));
+    assert_eq!(size(5 * GIB).to_string(), "5368709120");
+    assert_eq!("1..5368709120".parse(), Ok(range(1..5 * GIB)));
+    assert_eq!(range(1..5 * GIB).to_string(), "1..5368709120");
+    assert!(matches!(
+        "5..=3".parse::<TextRange64>(),
+        Err(ParseRangeError::Invalid(_))
+    ));
+}
+
+#[test]
+fn of() {
+    assert_eq!(TextSize64::of("hello"), size(5));
+    assert_eq!(TextSize64::of('🦀'), size(4));
+    assert_eq!(TextSize64::of(&String::from("größe")), size(7));
+    assert_eq!(TextSize64::of(&b"bytes"[..]), size(5));
+}
+
+#[test]
+fn char_boundaries() {
+    let text = "a🦀b";
+    assert_eq!(size(3).floor_char_boundary(text), size(1));
+    assert_eq!(size(3).ceil_char_boundary(text), size(5));
+    assert_eq!(size(5 * GIB).floor_char_boundary(text), size(6));
+    assert_eq!(size(5 * GIB).ceil_char_boundary(text), size(6));
+}
+
+#[test]
+fn indexing() {
+    let text = "hello world";
+    assert_eq!(&text[range(6..11)], "world");
+    assert_eq!(&text.to_string()[range(6..11)], "world");
+    assert_eq!(range(6..11).get(text), Some("world"));
+    assert_eq!(range(6..12).get(text), None);
+    assert_eq!(range(6..5 * GIB).get(text), None);
+
+    let mut bytes = text.as_bytes().to_vec();
+    assert_eq!(&bytes[range(0..5)], b"hello");
+    assert_eq!(&bytes[..][range(0..5)], b"hello");
+    bytes[range(0..1)].make_ascii_uppercase();
+    assert_eq!(range(0..5).get_bytes(&bytes), Some(&b"Hello"[..]));
+    assert_eq!(range(0..5 * GIB).get_bytes(&bytes), None);
+}
+
+#[test]
+fn checked_for() {
+    let text = "größe";
+    assert_eq!(range(0..2).checked_for(text), Ok(range(0..2)));
+    assert_eq!(
+        range(0..3).checked_for(text),
+        Err(RangeError::NotCharBoundary { offset: size(3) })
+    );
+    assert_eq!(
+        range(0..5 * GIB).checked_for(text),
+        Err(RangeError::OutOfBounds {
+            range: range(0..5 * GIB),
+            len: size(7),
+        })
+    );
+}
+
+#[test]
+#[should_panic]
+fn index_out_of_bounds() {
+    let _ = &"hello"[range(0..5 * GIB)];
+}
@@ -0,0 +1,269 @@
+use {std::ops, text_size::*};
+
+fn range(x: ops::Range<u32>) -> TextRange {
+    TextRange::new(x.start.into(), x.end.into())
+}
+
+const MAIN: &str = "fn main() {\n    let x = foo(bar);\n    baz();\n}\n";
+
+fn check(snippet: &Snippet<'_>, expected: &str) {
+    let expected = expected.strip_prefix('\n').unwrap();
+    assert_eq!(snippet.to_string(), expected);
+}
+
+#[test]
+fn single_line() {
+    let mut snippet = Snippet::new(MAIN, range(28..31), "not found");
+    snippet.label(range(24..27), "in this call");
+    snippet.origin("src/main.rs");
+    check(
+        &snippet,
+        r"
+ --> src/main.rs:2:17
+  |
+2 |     let x = foo(bar);
+  |             --- ^^^ not found
+  |             |
+  |             in this call",
+    );
+}
+
+#[test]
+fn overlapping_labels() {
+    let mut snippet = Snippet::new(MAIN, range(24..32), "call");
+    snippet.label(range(28..31), "arg");
+    check(
+        &snippet,
+        r"
+  |
+2 |     let x = foo(bar);
+  |             ^^^^^^^^
+  |             |   |
+  |             |   arg
+  |             call",
+    );
+}
+
+#[test]
+fn multi_line() {
+    let snippet = Snippet::new(MAIN, range(10..47), "body");
+    check(
+        &snippet,
+        r"
+  |
+1 |   fn main() {
+  |  ___________^
+2 | |     let x = foo(bar);
+3 | |     baz();
+4 | | }
+  | |__^ body",
+    );
+
+    let snippet = Snippet::new(MAIN, range(0..47), "whole fn");
+    check(
+        &snippet,
+        r"
+  |
+1 | / fn main() {
+2 | |     let x = foo(bar);
+3 | |     baz();
+4 | | }
+  | |__^ whole fn",
+    );
+}
+
+#[test]
+fn nested_multi_line() {
+    let mut snippet = Snippet::new(MAIN, range(16..42), "inner");
+    snippet.label(range(10..47), "outer");
+    check(
+        &snippet,
+        r"
+  |
+1 |     fn main() {
+  |  _____________-
+2 | | /     let x = foo(bar);
+3 | | |     baz();
+  | | |________^ inner
+4 | |   }
+  | |____- outer",
+    );
+}
+
+#[test]
+fn single_line_inside_multi_line() {
+    let mut snippet = Snippet::new(MAIN, range(28..31), "here");
+    snippet.label(range(0..47), "in this fn");
+    check(
+        &snippet,
+        r"
+  |
+1 | / fn main() {
+2 | |     let x = foo(bar);
+  | |                 ^^^ here
+3 | |     baz();
+4 | | }
+  | |__- in this fn",
+    );
+}
+
+#[test]
+fn elided_lines() {
+    let text: String = (0..20).map(|i| format!("line {}\n", i)).collect();
+    let mut snippet = Snippet::new(&text, range(0..4), "first");
+    snippet.label(range(68..69), "later");
+    check(
+        &snippet,
+        r"
+   |
+ 1 | line 0
+   | ^^^^ first
+...
+10 | line 9
+   |      - later",
+    );
+
+    let snippet = Snippet::new(&text, range(14..86), "multi");
+    check(
+        &snippet,
+        r"
+   |
+ 3 | / line 2
+...
+12 | | line 11
+   | |________^ multi",
+    );
+}
+
+#[test]
+fn tabs() {
+    let text = "\tlet x =\t1;\n";
+    let mut snippet = Snippet::new(text, range(9..10), "one");
+    snippet.label(range(1..4), "");
+    check(
+        &snippet,
+        r"
+  |
+1 |     let x = 1;
+  |     ---     ^ one",
+    );
+
+    let mut snippet = Snippet::new(text, range(9..10), "one");
+    snippet.tab_width(2);
+    check(
+        &snippet,
+        r"
+  |
+1 |   let x = 1;
+  |           ^ one",
+    );
+}
+
+#[test]
+fn unicode() {
+    let text = "let s = \"日本語🦀é\"; s.foo();\n";
+    let foo = TextSize::of("let s = \"日本語🦀é\"; s.");
+    let mut snippet = Snippet::new(text, TextRange::at(foo, 3.into()), "no method");
+    snippet.label(range(8..25), "string");
+    snippet.origin("lib.rs");
+    check(
+        &snippet,
+        r#"
+ --> lib.rs:1:20
+  |
+1 | let s = "日本語🦀é"; s.foo();
+  |         -----------    ^^^ no method
+  |         |
+  |         string"#,
+    );
+}
+
+#[test]
+fn crlf() {
+    let text = "a = 1\r\nb = a +\r\n  2\r\n";
+    let mut snippet = Snippet::new(text, range(11..20), "sum");
+    snippet.origin("x.txt");
+    check(
+        &snippet,
+        r"
+ --> x.txt:2:5
+  |
+2 |   b = a +
+  |  _____^
+3 | |   2
+  | |____^ sum",
+    );
+
+    let snippet = Snippet::new(text, range(5..7), "newline");
+    check(
+        &snippet,
+        r"
+  |
+1 | a = 1
+  |      ^ newline",
+    );
+}
+
+#[test]
+fn control_chars() {
+    let text = "a\rb\x1b[2Jc\x7f\n";
+    let snippet = Snippet::new(text, range(2..3), "here");
+    check(
+        &snippet,
+        r"
+  |
+1 | a␍b␛[2Jc␡
+  |   ^ here",
+    );
+}
+
+#[test]
+fn multi_line_ending_in_zero_width_char() {
+    let text = "ab\n\u{301}c\n";
+    let snippet = Snippet::new(text, range(1..5), "accent");
+    check(
+        &snippet,
+        r"
+  |
+1 |   ab
+  |  __^
+2 | | ́c
+  | |_^ accent",
+    );
+}
+
+#[test]
+fn empty_range() {
+    let snippet = Snippet::new(MAIN, range(31..31), "expected `;`");
+    check(
+        &snippet,
+        r"
+  |
+2 |     let x = foo(bar);
+  |                    ^ expected `;`",
+    );
+}
+
+#[test]
+fn color() {
+    let mut snippet = Snippet::new(MAIN, range(28..31), "here");
+    snippet.color(true);
+    let rendered = snippet.to_string();
+    assert!(rendered.contains("\x1b[1;31m^^^ here\x1b[0m"));
+    assert!(rendered.contains("\x1b[1;34m2 | \x1b[0m    let x = foo(bar);"));
+
+    snippet.color(false);
+    assert!(!snippet.to_string().contains('\x1b'));
+}
+
+#[test]
+#[should_panic(expected = "invalid label range")]
+fn out_of_bounds_label() {
+    Snippet::new(MAIN, range(0..100), "oops");
+}
+
+#[test]
+#[should_panic(expected = "invalid label range")]
+fn label_not_on_char_boundary() {
+    Snippet::new("é", range(0..1), "oops");
+}
@@ -0,0 +1,109 @@
+use {std::ops, text_size::*};
+
+fn size(x: u32) -> TextSize {
+    TextSize::from(x)
+}
+
+fn range(x: ops::Range<u32>) -> TextRange {
+    TextRange::new(x.start.into(), x.end.into())
+}
+
+fn three_files() -> (SourceMap, [FileId; 3]) {
+    let mut map = SourceMap::new();
+    let a = map.add_file(size(3));
+    let b = map.add_file(size(0));
+    let c = map.add_file(size(2));
+    (map, [a, b, c])
+}
+
+#[test]
+fn layout() {
+    let (map, [a, b, c]) = three_files();
+    assert_eq!(map.len(), 3);
+    assert_eq!(
+        map.iter().collect::<Vec<_>>(),
+        vec![(a, range(0..3)), (b, range(4..4)), (c, range(5..7))]
+    );
+}
+
+#[test]
+fn offsets() {
+    let (map, [a, b, c]) = three_files();
+    let expected = [
+        (a, 0),
+        (a, 1),
+        (a, 2),
+        (a, 3),
+        (b, 0),
+        (c, 0),
+        (c, 1),
+        (c, 2),
+    ];
+    for (global, &(file, local)) in expected.iter().enumerate() {
+        let global = size(global as u32);
+        assert_eq!(map.lookup(global), Some((file, size(local))));
+        assert_eq!(map.to_global(file, size(local)), Some(global));
+    }
+    assert_eq!(map.lookup(size(8)), None);
+    assert_eq!(map.to_global(a, size(4)), None);
+    assert_eq!(map.to_global(b, size(1)), None);
+}
+
+#[test]
+fn ranges() {
+    let (map, [a, _, c]) = three_files();
+    assert_eq!(
+        map.lookup_range(range(1..3)),
+        Some(FileRange::new(a, range(1..3)))
+    );
+    assert_eq!(
+        map.lookup_range(range(5..7)),
+        Some(FileRange::new(c, range(0..2)))
+    );
+    assert_eq!(map.lookup_range(range(2..5)), None);
+    assert_eq!(map.lookup_range(range(7..9)), None);
+
+    assert_eq!(
+        map.to_global_range(FileRange::new(c, range(1..2))),
+        Some(range(6..7))
+    );
+    assert_eq!(map.to_global_range(FileRange::new(c, range(1..3))), None);
+}
+
+#[test]
+fn unknown_file() {
+    let (map, _) = three_files();
+    let (mut other, _) = three_files();
+    let d = other.add_file(size(1));
+    assert_eq!(map.file_range(d), None);
+    assert_eq!(map.to_global(d, size(0)), None);
+    assert_eq!(map.to_global_range(FileRange::new(d, range(0..0))), None);
+}
+
+#[test]
+fn file_ranges() {
+    let (_, [a, b, _]) = three_files();
+    let x = FileRange::new(a, range(1..3));
+    let y = FileRange::new(a, range(2..5));
+    let z = FileRange::new(b, range(2..5));
+
+    assert!(x.contains(a, size(1)));
+    assert!(!x.contains(b, size(1)));
+    assert!(!x.contains(a, size(3)));
+
+    assert!(y.contains_range(FileRange::new(a, range(3..4))));
+    assert!(!z.contains_range(FileRange::new(a, range(3..4))));
+
+    assert_eq!(x.intersect(y), Some(FileRange::new(a, range(2..3))));
+    assert_eq!(x.intersect(z), None);
+    assert_eq!(x.cover(y), Some(FileRange::new(a, range(1..5))));
+    assert_eq!(x.cover(z), None);
+}
+
+#[test]
+#[should_panic]
+fn overflow() {
+    let mut map = SourceMap::new();
+    map.add_file(size(u32::MAX));
+    map.add_file(size(0));
+}
@@ -0,0 +1,109 @@
+use {std::ops, text_size::*};
+
+fn size(x: u32) -> Tagged<TextSize> {
+    Tagged::new(TextSize::from(x))
+}
+
+fn range(x: ops::Range<u32>) -> Tagged<TextRange> {
+    Tagged::new(TextRange::new(x.start.into(), x.end.into()))
+}
+
+#[test]
+fn offset_round_trip() {
+    let parent = range(10..20);
+    for offset in 10..=20 {
+        let relative = size(offset).relative_to(parent).unwrap();
+        assert_eq!(relative.value(), TextSize::from(offset - 10));
+        assert_eq!(relative.absolute_from(parent), Some(size(offset)));
+    }
+    assert_eq!(size(9).relative_to(parent), None);
+    assert_eq!(size(21).relative_to(parent), None);
+
+    let past_end: Tagged<TextSize, Relative> = Tagged::new(11.into());
+    assert_eq!(past_end.absolute_from(parent), None);
+}
+
+#[test]
+fn range_round_trip() {
+    let parent = range(10..20);
+    let child = range(12..20);
+    let relative = child.relative_to(parent).unwrap();
+    assert_eq!(relative.value(), TextRange::new(2.into(), 10.into()));
+    assert_eq!(relative.absolute_from(parent), Some(child));
+
+    assert_eq!(range(5..12).relative_to(parent), None);
+    assert_eq!(range(12..21).relative_to(parent), None);
+
+    let past_end: Tagged<TextRange, Relative> = Tagged::new(TextRange::new(5.into(), 11.into()));
+    assert_eq!(past_end.absolute_from(parent), None);
+}
+
+#[test]
+fn same_space_methods() {
+    let a = range(2..6);
+    let b = range(4..8);
+    assert_eq!(a.start(), size(2));
+    assert_eq!(a.end(), size(6));
+    assert_eq!(a.len(), TextSize::from(4));
+    assert!(a.contains(size(2)));
+    assert!(!a.contains(size(6)));
+    assert!(a.contains_range(range(3..5)));
+    assert_eq!(a.intersect(b), Some(range(4..6)));
+    assert_eq!(a.cover(b), range(2..8));
+    assert_eq!(b.end() - a.start(), TextSize::from(6));
+    assert_eq!(a.start() + TextSize::from(3), size(5));
+    assert!(a.start() < b.start());
+}
+
+#[test]
+fn more_same_space_methods() {
+    let a = range(2..6);
+    assert_eq!(Tagged::try_new(size(2), size(6)), Ok(a));
+    assert!(Tagged::try_new(size(6), size(2)).is_err());
+    assert_eq!(Tagged::at(size(2), 4.into()), a);
+    assert_eq!(Tagged::empty(size(3)), range(3..3));
+    assert_eq!(Tagged::up_to(size(6)), range(0..6));
+
+    assert!(a.contains_inclusive(size(6)));
+    assert!(!a.contains_inclusive(size(7)));
+    assert_eq!(a.cover_offset(size(9)), range(2..9));
+    assert_eq!(
+        a.subtract(range(3..4)),
+        (Some(range(2..3)), Some(range(4..6)))
+    );
+    assert_eq!(a.split_at(size(3)), (range(2..3), range(3..6)));
+    assert_eq!(a.gap(range(8..9)), Some(range(6..8)));
+    assert_eq!(a.clamp(size(9)), size(6));
+    assert_eq!(a.ordering(range(6..8)), std::cmp::Ordering::Less);
+
+    assert_eq!(a.checked_add(2.into()), Some(range(4..8)));
+    assert_eq!(a.checked_sub(3.into()), None);
+    assert_eq!(size(2).checked_sub(3.into()), None);
+    assert_eq!(size(2).checked_add(3.into()), Some(size(5)));
+
+    let mut b = a + TextSize::from(1);
+    assert_eq!(b, range(3..7));
+    b -= TextSize::from(3);
+    assert_eq!(b, range(0..4));
+    let mut offset = size(1);
+    offset += TextSize::from(2);
+    assert_eq!(offset, size(3));
+}
+
+#[test]
+fn slicing() {
+    let text = "hello world";
+    let word = range(6..11);
+    assert_eq!(&text[word], "world");
+    assert_eq!(&text.to_string()[word], "world");
+    assert_eq!(&text.as_bytes()[word], b"world");
+    assert_eq!(word.get(text), Some("world"));
+    assert_eq!(word.get_bytes(text.as_bytes()), Some(&b"world"[..]));
+    assert_eq!(word.checked_for(text), Ok(word));
+    assert_eq!(range(6..12).get(text), None);
+    assert!(range(6..12).checked_for(text).is_err());
+
+    let mut owned = String::from(text);
+    owned[word].make_ascii_uppercase();
+    assert_eq!(owned, "hello WORLD");
+}
@@ -0,0 +1,177 @@
+mod common;
+
+use {common::Rng, std::ops, text_size::*};
+
+fn size(x: u32) -> TextSize {
+    TextSize::from(x)
+}
+
+fn range(x: ops::Range<u32>) -> TextRange {
+    TextRange::new(x.start.into(), x.end.into())
+}
+
+fn edit(indels: &[(ops::Range<u32>, &str)]) -> Result<TextEdit, OverlappingIndels> {
+    let mut builder = TextEdit::builder();
+    for (delete, insert) in indels {
+        builder.replace(range(delete.clone()), insert.to_string());
+    }
+    builder.finish()
+}
+
+#[test]
+fn apply() {
+    let edit = edit(&[(4..5, ""), (0..0, "> "), (1..3, "XY"), (9..9, "!")]).unwrap();
+    let mut text = String::from("0123456789");
+    edit.apply(&mut text);
+    assert_eq!(text, "> 0XY35678!9");
+
+    let mut text = String::from("abc");
+    TextEdit::default().apply(&mut text);
+    assert_eq!(text, "abc");
+}
+
+#[test]
+fn indels_are_sorted() {
+    let edit = edit(&[(3..5, "c"), (3..3, "b"), (0..1, "a")]).unwrap();
+    let ranges: Vec<TextRange> = edit.iter().map(|indel| indel.delete).collect();
+    assert_eq!(ranges, vec![range(0..1), range(3..3), range(3..5)]);
+}
+
+#[test]
+fn insertions_at_same_offset_keep_order() {
+    let edit = edit(&[(1..1, "a"), (1..1, "b"), (1..1, "c")]).unwrap();
+    let mut text = String::from("01");
+    edit.apply(&mut text);
+    assert_eq!(text, "0abc1");
+}
+
+#[test]
+fn overlapping_indels() {
+    let err = edit(&[(0..3, "a"), (2..5, "b")]).unwrap_err();
+    assert_eq!(err.ranges(), (range(0..3), range(2..5)));
+    assert_eq!(err.to_string(), "overlapping indels: 0..3 and 2..5");
+
+    assert!(edit(&[(0..3, ""), (1..1, "x")]).is_err());
+    assert!(edit(&[(0..3, "a"), (0..3, "b")]).is_err());
+    assert!(edit(&[(0..3, ""), (3..3, "x"), (3..4, "")]).is_ok());
+}
+
+#[test]
+fn apply_to_offset() {
+    let edit = edit(&[(2..4, "xyz"), (6..6, "ab"), (8..10, "")]).unwrap();
+    let expected = [
+        Some(0),
+        Some(1),
+        Some(2),
+        None,
+        Some(5),
+        Some(6),
+        Some(7),
+        Some(10),
+        Some(11),
+        None,
+        Some(11),
+        Some(12),
+    ];
+    for (offset, &expected) in expected.iter().enumerate() {
+        assert_eq!(
+            edit.apply_to_offset(size(offset as u32)),
+            expected.map(size),
+            "offset: {}",
+            offset,
+        );
+    }
+}
+
+#[test]
+fn apply_to_range() {
+    let edit = edit(&[(2..4, "xyz"), (6..6, "ab")]).unwrap();
+    assert_eq!(edit.apply_to_range(range(0..2)), Some(range(0..2)));
+    assert_eq!(edit.apply_to_range(range(4..6)), Some(range(5..7)));
+    assert_eq!(edit.apply_to_range(range(0..7)), Some(range(0..10)));
+    assert_eq!(edit.apply_to_range(range(3..7)), None);
+}
+
+const ALPHABET: &[char] = &['a', 'b', 'c', 'é', '🦀'];
+
+/// A random edit of `text`, with replacements on char boundaries.
+fn random_edit(rng: &mut Rng, text: &str) -> TextEdit {
+    let mut boundaries: Vec<u32> = text
+        .char_indices()
+        .map(|(i, _)| i as u32)
+        .chain(Some(text.len() as u32))
+        .filter(|_| rng.below(3) == 0)
+        .collect();
+    if boundaries.len() % 2 == 1 {
+        boundaries.push(boundaries[boundaries.len() - 1]);
+    }
+
+    let mut builder = TextEdit::builder();
+    for pair in boundaries.chunks(2) {
+        builder.replace(range(pair[0]..pair[1]), rng.text(ALPHABET, 3));
+    }
+    builder.finish().unwrap()
+}
+
+fn applied(text: &str, edit: &TextEdit) -> String {
+    let mut text = text.to_string();
+    edit.apply(&mut text);
+    text
+}
+
+#[test]
+fn compose() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    for _ in 0..2000 {
+        let text = rng.text(ALPHABET, 10);
+        let a = random_edit(&mut rng, &text);
+        let b = random_edit(&mut rng, &applied(&text, &a));
+        assert_eq!(
+            applied(&text, &a.compose(&b)),
+            applied(&applied(&text, &a), &b),
+            "text: {:?}, a: {:?}, b: {:?}",
+            text,
+            a,
+            b,
+        );
+    }
+}
+
+#[test]
+fn invert() {
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..2000 {
+        let text = rng.text(ALPHABET, 10);
+        let edit = random_edit(&mut rng, &text);
+        let inverse = edit.invert(&text);
+        assert_eq!(applied(&applied(&text, &edit), &inverse), text);
+        assert_eq!(inverse.invert(&applied(&text, &edit)), edit);
+    }
+}
+
+#[test]
+fn transform_converges() {
+    let mut rng = Rng(0xd1b5_4a32_d192_ed03);
+    for _ in 0..2000 {
+        let text = rng.text(ALPHABET, 10);
+        let a = random_edit(&mut rng, &text);
+        let b = random_edit(&mut rng, &text);
+        for &side in [Side::Left, Side::Right].iter() {
+            let after_a = applied(&applied(&text, &a), &b.transform(&a, side.opposite()));
+            let after_b = applied(&applied(&text, &b), &a.transform(&b, side));
+            assert_eq!(
+                after_a, after_b,
+                "text: {:?}, a: {:?}, b: {:?}, side: {:?}",
+                text, a, b, side,
+            );
+        }
+    }
+}
+
+#[test]
+fn transform_keeps_intent() {
+    let a = edit(&[(2..4, "")]).unwrap();
+    let b = edit(&[(0..0, ">"), (3..3, "!")]).unwrap();
+    assert_eq!(applied("0123", &b), ">012!3");
+    assert_eq!(applied(">012!3", &a.transform(&b, Side::Left)), ">01!");
+}
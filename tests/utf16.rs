@@ -0,0 +1,106 @@
+use {
+    std::{cmp::Ordering, ops},
+    text_size::*,
+};
+
+fn size(x: u32) -> Utf16Size {
+    Utf16Size::from(x)
+}
+
+fn range(x: ops::Range<u32>) -> Utf16Range {
+    Utf16Range::new(x.start.into(), x.end.into())
+}
+
+#[test]
+fn of() {
+    assert_eq!(Utf16Size::of(""), size(0));
+    assert_eq!(Utf16Size::of("abc"), size(3));
+    assert_eq!(Utf16Size::of("é"), size(1));
+    assert_eq!(Utf16Size::of('𐐀'), size(2));
+    assert_eq!(Utf16Size::of(&"a𐐀b".to_string()), size(4));
+}
+
+#[test]
+fn arithmetic() {
+    assert_eq!(size(2) + size(3), size(5));
+    assert_eq!(size(5) - size(3), size(2));
+    assert_eq!(size(1).checked_sub(size(2)), None);
+    assert_eq!(
+        vec![size(1), size(2)].into_iter().sum::<Utf16Size>(),
+        size(3)
+    );
+    assert_eq!(range(1..3) + size(2), range(3..5));
+    assert_eq!(range(1..3).len(), size(2));
+    assert_eq!(range(1..3).cover(range(5..6)), range(1..6));
+    assert_eq!(range(1..3).intersect(range(2..6)), Some(range(2..3)));
+}
+
+#[test]
+fn same_api_as_text_range() {
+    const R: Utf16Range = Utf16Range::at(Utf16Size::new(2), Utf16Size::new(3));
+    assert_eq!(R, range(2..5));
+    assert_eq!(Utf16Range::up_to(size(4)), range(0..4));
+    assert_eq!(Utf16Range::try_new(size(1), size(3)), Ok(range(1..3)));
+    let err = Utf16Range::try_new(size(3), size(1)).unwrap_err();
+    assert_eq!((err.start(), err.end()), (size(3), size(1)));
+
+    assert_eq!(size(7) * 2, size(14));
+    assert_eq!(size(7) % size(4), size(3));
+    assert_eq!(Utf16Size::MAX.checked_add(size(1)), None);
+    assert_eq!(size(1).saturating_sub(size(2)), Utf16Size::ZERO);
+
+    assert_eq!(
+        range(0..10).subtract(range(3..5)),
+        (Some(range(0..3)), Some(range(5..10)))
+    );
+    assert_eq!(range(0..10).split_at(size(4)), (range(0..4), range(4..10)));
+    assert_eq!(range(0..3).gap(range(5..6)), Some(range(3..5)));
+    assert_eq!(range(0..3).ordering(range(3..5)), Ordering::Less);
+    assert_eq!(range(2..5).clamp(size(9)), size(5));
+}
+
+#[test]
+fn parse_and_display() {
+    assert_eq!("7".parse(), Ok(size(7)));
+    assert_eq!(size(7).to_string(), "7");
+    assert_eq!("1..=4".parse(), Ok(range(1..5)));
+    assert_eq!(range(1..5).to_string(), "1..5");
+    assert!(matches!(
+        "5..1".parse::<Utf16Range>(),
+        Err(ParseRangeError::Invalid(_))
+    ));
+}
+
+#[test]
+fn conversions() {
+    let text = "a\u{e9}\u{10400}b";
+    // UTF-8 offsets of char boundaries, and the UTF-16 offsets they map to.
+    let boundaries = [(0, 0), (1, 1), (3, 2), (7, 4), (8, 5)];
+    for &(utf8, utf16) in &boundaries {
+        let utf8 = TextSize::from(utf8);
+        assert_eq!(Utf16Size::from_text_size(text, utf8), Some(size(utf16)));
+        assert_eq!(size(utf16).to_text_size(text), Some(utf8));
+    }
+
+    for &utf8 in &[2, 4, 5, 6, 9] {
+        assert_eq!(Utf16Size::from_text_size(text, utf8.into()), None);
+    }
+    for &utf16 in &[3, 6] {
+        assert_eq!(size(utf16).to_text_size(text), None);
+    }
+}
+
+#[test]
+fn range_conversions() {
+    let text = "a\u{e9}\u{10400}b";
+    let utf8 = TextRange::new(1.into(), 7.into());
+    assert_eq!(Utf16Range::from_text_range(text, utf8), Some(range(1..4)));
+    assert_eq!(range(1..4).to_text_range(text), Some(utf8));
+
+    assert_eq!(
+        Utf16Range::from_text_range(text, TextRange::new(1.into(), 4.into())),
+        None
+    );
+    assert_eq!(range(3..4).to_text_range(text), None);
+    assert_eq!(range(4..6).to_text_range(text), None);
+}